@@ -0,0 +1,45 @@
+// Compares the day 16 solvers on the real input:
+//
+//     cargo bench --bench day16
+//
+// The search takes minutes for two actors, so that case only runs, once, with AOC_BENCH_SLOW set.
+
+use aoc::bench::fastest;
+use aoc::days::day16::{Solver, ValveOptimizer};
+
+const RUNS: u32 = 5;
+
+fn main() {
+    let input = std::fs::read_to_string("data/day16.txt").expect("puzzle input in data/");
+    let slow = std::env::var_os("AOC_BENCH_SLOW").is_some();
+
+    for (actors, minutes) in [(1, 30), (2, 26)] {
+        for solver in [Solver::Bitmask, Solver::Search] {
+            let runs = match (actors, solver) {
+                (1, _) | (_, Solver::Bitmask) => RUNS,
+                _ if slow => 1,
+                _ => {
+                    println!(
+                        "{} actor(s), {} minutes, {:?}: skipped, set AOC_BENCH_SLOW to run it",
+                        actors, minutes, solver
+                    );
+                    continue;
+                }
+            };
+
+            let optimizer = ValveOptimizer::new(actors, minutes).with_solver(solver);
+
+            let mut released = 0;
+            let duration = fastest(runs, || {
+                released = optimizer.optimize(&input)?.released;
+                Ok(())
+            })
+            .expect("day 16 solves");
+
+            println!(
+                "{} actor(s), {} minutes, {:?}: {} in {:.3?}",
+                actors, minutes, solver, released, duration
+            );
+        }
+    }
+}
@@ -0,0 +1,166 @@
+use std::collections::BTreeMap;
+use std::hint::black_box;
+use std::str::FromStr;
+use std::time::{Duration, Instant};
+
+use crate::solution::{Part, Solution, SolveError};
+
+// What gets timed for each day. The parts parse their own input, so their timings include
+// parsing too; the parse phase shows how much of that it is.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug)]
+pub enum Phase {
+    Parse,
+    Solve(Part),
+}
+
+impl std::fmt::Display for Phase {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Phase::Parse => write!(f, "parse"),
+            Phase::Solve(part) => write!(f, "part{}", part),
+        }
+    }
+}
+
+impl FromStr for Phase {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Phase, String> {
+        match s {
+            "parse" => Ok(Phase::Parse),
+            "part1" => Ok(Phase::Solve(Part::One)),
+            "part2" => Ok(Phase::Solve(Part::Two)),
+            _ => Err(format!("invalid phase: {:?}", s)),
+        }
+    }
+}
+
+// The fastest of `runs` runs of each phase, which is the one least disturbed by whatever else
+// the machine was doing at the time.
+pub fn measure(
+    solution: &dyn Solution,
+    input: &str,
+    runs: u32,
+) -> Result<Vec<(Phase, Duration)>, SolveError> {
+    let mut timings = vec![(Phase::Parse, fastest(runs, || solution.parse(input))?)];
+
+    for &part in solution.parts() {
+        let duration = fastest(runs, || {
+            solution.solve(part, input).map(black_box).map(drop)
+        })?;
+        timings.push((Phase::Solve(part), duration));
+    }
+
+    Ok(timings)
+}
+
+pub fn fastest<F>(runs: u32, mut f: F) -> Result<Duration, SolveError>
+where
+    F: FnMut() -> Result<(), SolveError>,
+{
+    let mut best = Duration::MAX;
+
+    for _ in 0..runs.max(1) {
+        let start = Instant::now();
+        f()?;
+        best = best.min(start.elapsed());
+    }
+
+    Ok(best)
+}
+
+// Timings saved from an earlier run, one `<day> <phase> <nanoseconds>` line per phase.
+#[derive(Default, PartialEq, Debug)]
+pub struct Baseline {
+    timings: BTreeMap<(u8, Phase), Duration>,
+}
+
+impl Baseline {
+    pub fn insert(&mut self, day: u8, phase: Phase, duration: Duration) {
+        self.timings.insert((day, phase), duration);
+    }
+
+    pub fn get(&self, day: u8, phase: Phase) -> Option<Duration> {
+        self.timings.get(&(day, phase)).copied()
+    }
+}
+
+impl std::fmt::Display for Baseline {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for ((day, phase), duration) in &self.timings {
+            writeln!(f, "{} {} {}", day, phase, duration.as_nanos())?;
+        }
+
+        Ok(())
+    }
+}
+
+impl FromStr for Baseline {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Baseline, String> {
+        let mut baseline = Baseline::default();
+
+        for (line, n) in s.lines().zip(1..) {
+            let fields: Vec<_> = line.split_whitespace().collect();
+            let [day, phase, nanos] = fields[..] else {
+                return Err(format!("line {}: expected <day> <phase> <nanoseconds>", n));
+            };
+
+            let day = day
+                .parse()
+                .map_err(|_| format!("line {}: invalid day: {:?}", n, day))?;
+            let phase = phase.parse().map_err(|e| format!("line {}: {}", n, e))?;
+            let nanos = nanos
+                .parse()
+                .map_err(|_| format!("line {}: invalid duration: {:?}", n, nanos))?;
+
+            baseline.insert(day, phase, Duration::from_nanos(nanos));
+        }
+
+        Ok(baseline)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::days::day01::Day01;
+
+    #[test]
+    fn test_baseline_round_trip() {
+        let mut baseline = Baseline::default();
+        baseline.insert(1, Phase::Parse, Duration::from_micros(52));
+        baseline.insert(1, Phase::Solve(Part::One), Duration::from_micros(80));
+        baseline.insert(25, Phase::Solve(Part::One), Duration::from_nanos(1234));
+
+        let saved = baseline.to_string();
+        assert_eq!(saved, "1 parse 52000\n1 part1 80000\n25 part1 1234\n");
+        assert_eq!(saved.parse::<Baseline>().unwrap(), baseline);
+    }
+
+    #[test]
+    fn test_baseline_invalid_line() {
+        assert!("1 parse 52000\n1 part3 80000\n"
+            .parse::<Baseline>()
+            .unwrap_err()
+            .starts_with("line 2:"));
+        assert!("1 parse".parse::<Baseline>().is_err());
+    }
+
+    #[test]
+    fn test_measure_every_phase() {
+        let timings = measure(&Day01, "1000\n\n2000\n\n3000\n", 2).unwrap();
+        let phases: Vec<_> = timings.into_iter().map(|(phase, _)| phase).collect();
+
+        assert_eq!(
+            phases,
+            vec![
+                Phase::Parse,
+                Phase::Solve(Part::One),
+                Phase::Solve(Part::Two)
+            ]
+        );
+    }
+}
@@ -0,0 +1,415 @@
+use std::io::IsTerminal;
+use std::process::ExitCode;
+use std::time::Duration;
+
+use aoc::bench::{self, Baseline, Phase};
+use aoc::days::day16::{Network, ValveDiagram, ValveOptimizer};
+use aoc::input::{self, Source};
+use aoc::{days, Answer, Part, Solution, SolveError};
+
+const USAGE: &str = "\
+Usage:
+    aoc list
+    aoc run <day> [--part <1|2>] [--input <path|->]
+    aoc dot 16 [--network <tunnels|valves>] [--plan <1|2>] [--input <path|->]
+    aoc bench [<day>...] [--runs <n>] [--data <dir>] [--save <path>] [--baseline <path>]
+              [--threshold <percent>]
+
+Without --part every part of the day is run. Without --input the puzzle input is read from
+stdin if something is piped in, or from data/dayNN.txt if not. With --input - it is always
+read from stdin.
+
+dot writes day 16's valves as a Graphviz graph to stdout: every tunnel (the default), or only
+the valves worth opening with the minutes between them. --plan draws the best routes for that
+part on top. It reads its input the same way as run.
+
+The benchmark reads each day's input from <dir>/dayNN.txt (data/ by default) and times every
+day unless days are given. Each phase is run --runs times (default 1) and the fastest run is
+kept. --save writes the timings for a later --baseline, which marks anything more than
+--threshold percent (default 20) slower than the baseline as a regression.";
+
+const DEFAULT_THRESHOLD: f64 = 20.0;
+
+enum Command {
+    List,
+    Run {
+        day: u8,
+        part: Option<Part>,
+        input: Source,
+    },
+    Dot {
+        day: u8,
+        network: Network,
+        plan: Option<Part>,
+        input: Source,
+    },
+    Bench {
+        days: Vec<u8>,
+        runs: u32,
+        data: String,
+        save: Option<String>,
+        baseline: Option<String>,
+        threshold: f64,
+    },
+}
+
+fn parse_args<I>(mut args: I) -> Result<Command, String>
+where
+    I: Iterator<Item = String>,
+{
+    match args.next().as_deref() {
+        Some("list") => Ok(Command::List),
+        Some("run") => {
+            let day = args.next().ok_or("missing day")?;
+            let day = day.parse().map_err(|_| format!("invalid day: {:?}", day))?;
+
+            let mut part = None;
+            let mut input = None;
+
+            while let Some(arg) = args.next() {
+                match arg.as_str() {
+                    "--part" => {
+                        let value = args.next().ok_or("missing value for --part")?;
+                        part = Some(value.parse()?);
+                    }
+                    "--input" => {
+                        let value = args.next().ok_or("missing value for --input")?;
+                        input = Some(input_source(&value));
+                    }
+                    _ => return Err(format!("unexpected argument: {:?}", arg)),
+                }
+            }
+
+            let input = input.unwrap_or_else(|| default_input(day));
+
+            Ok(Command::Run { day, part, input })
+        }
+        Some("dot") => {
+            let day = args.next().ok_or("missing day")?;
+            let day = day.parse().map_err(|_| format!("invalid day: {:?}", day))?;
+
+            let mut network = Network::default();
+            let mut plan = None;
+            let mut input = None;
+
+            while let Some(arg) = args.next() {
+                match arg.as_str() {
+                    "--network" => {
+                        let value = args.next().ok_or("missing value for --network")?;
+                        network = value.parse()?;
+                    }
+                    "--plan" => {
+                        let value = args.next().ok_or("missing value for --plan")?;
+                        plan = Some(value.parse()?);
+                    }
+                    "--input" => {
+                        let value = args.next().ok_or("missing value for --input")?;
+                        input = Some(input_source(&value));
+                    }
+                    _ => return Err(format!("unexpected argument: {:?}", arg)),
+                }
+            }
+
+            let input = input.unwrap_or_else(|| default_input(day));
+
+            Ok(Command::Dot {
+                day,
+                network,
+                plan,
+                input,
+            })
+        }
+        Some("bench") => {
+            let mut days = vec![];
+            let mut runs = 1;
+            let mut data = input::DATA_DIR.to_owned();
+            let mut save = None;
+            let mut baseline = None;
+            let mut threshold = DEFAULT_THRESHOLD;
+
+            while let Some(arg) = args.next() {
+                match arg.as_str() {
+                    "--runs" => {
+                        let value = args.next().ok_or("missing value for --runs")?;
+                        runs = value
+                            .parse()
+                            .map_err(|_| format!("invalid number of runs: {:?}", value))?;
+                    }
+                    "--data" => {
+                        data = args.next().ok_or("missing value for --data")?;
+                    }
+                    "--save" => {
+                        save = Some(args.next().ok_or("missing value for --save")?);
+                    }
+                    "--baseline" => {
+                        baseline = Some(args.next().ok_or("missing value for --baseline")?);
+                    }
+                    "--threshold" => {
+                        let value = args.next().ok_or("missing value for --threshold")?;
+                        threshold = value
+                            .parse()
+                            .map_err(|_| format!("invalid threshold: {:?}", value))?;
+                    }
+                    _ => {
+                        let day = arg
+                            .parse()
+                            .map_err(|_| format!("unexpected argument: {:?}", arg))?;
+                        days.push(day);
+                    }
+                }
+            }
+
+            Ok(Command::Bench {
+                days,
+                runs,
+                data,
+                save,
+                baseline,
+                threshold,
+            })
+        }
+        Some(command) => Err(format!("unknown command: {:?}", command)),
+        None => Err("missing command".to_owned()),
+    }
+}
+
+fn input_source(value: &str) -> Source {
+    match value {
+        "-" => Source::Stdin,
+        path => Source::Path(path.into()),
+    }
+}
+
+// Anything piped in is what's wanted, so `aoc run 10 < input.txt` still works without `--input -`.
+fn default_input(day: u8) -> Source {
+    if std::io::stdin().is_terminal() {
+        Source::Day(day)
+    } else {
+        Source::Stdin
+    }
+}
+
+// Day 16's valves, as a Graphviz graph. No other day has a diagram.
+fn dot(network: Network, plan: Option<Part>, input: &str) -> Result<String, SolveError> {
+    let mut diagram = ValveDiagram::new(network);
+    if let Some(part) = plan {
+        diagram = diagram.with_plan(ValveOptimizer::for_part(part).optimize(input)?);
+    }
+
+    diagram.render(input)
+}
+
+fn run(solution: &dyn Solution, parts: &[Part], input: &str) -> ExitCode {
+    let mut code = ExitCode::SUCCESS;
+
+    for &part in parts {
+        match solution.solve(part, input) {
+            // Some answers (day 10) are pictures, which read better starting on their own line.
+            Ok(Answer::Text(answer)) if answer.contains('\n') => {
+                println!("Day {} part {}:\n{}", solution.day(), part, answer)
+            }
+            Ok(answer) => println!("Day {} part {}: {}", solution.day(), part, answer),
+            Err(e) => {
+                eprintln!("Day {} part {} failed: {}", solution.day(), part, e);
+                code = ExitCode::FAILURE;
+            }
+        }
+    }
+
+    code
+}
+
+// A timing, followed by how it compares to the baseline if there is one. The flag marks a
+// regression.
+fn format_timing(duration: Duration, baseline: Option<Duration>, threshold: f64) -> (String, bool) {
+    let Some(baseline) = baseline else {
+        return (format!("{:.1?}", duration), false);
+    };
+
+    let change = 100.0 * (duration.as_secs_f64() / baseline.as_secs_f64() - 1.0);
+    let regressed = change > threshold;
+    let cell = format!(
+        "{:.1?} {:+.0}%{}",
+        duration,
+        change,
+        if regressed { " !" } else { "" }
+    );
+
+    (cell, regressed)
+}
+
+fn bench(
+    solutions: &[&dyn Solution],
+    runs: u32,
+    data: &str,
+    baseline: Option<&Baseline>,
+    threshold: f64,
+) -> (Baseline, ExitCode) {
+    let mut code = ExitCode::SUCCESS;
+    let mut timings = Baseline::default();
+
+    println!("Day  {:<20}  {:<20}  Part 2", "Parse", "Part 1");
+
+    for &solution in solutions {
+        let day = solution.day();
+        let measured = Source::Path(input::day_path(data, day))
+            .load()
+            .map_err(|e| e.to_string())
+            .and_then(|input| bench::measure(solution, &input, runs).map_err(|e| e.to_string()));
+
+        let measured = match measured {
+            Ok(measured) => measured,
+            Err(e) => {
+                eprintln!("Day {} failed: {}", day, e);
+                code = ExitCode::FAILURE;
+                continue;
+            }
+        };
+
+        let mut cells = vec![];
+        for phase in [
+            Phase::Parse,
+            Phase::Solve(Part::One),
+            Phase::Solve(Part::Two),
+        ] {
+            let Some(&(_, duration)) = measured.iter().find(|(p, _)| *p == phase) else {
+                cells.push("-".to_owned());
+                continue;
+            };
+
+            let expected = baseline.and_then(|baseline| baseline.get(day, phase));
+            let (cell, regressed) = format_timing(duration, expected, threshold);
+            if regressed {
+                code = ExitCode::FAILURE;
+            }
+
+            cells.push(cell);
+            timings.insert(day, phase, duration);
+        }
+
+        println!(
+            "{:>3}  {:<20}  {:<20}  {}",
+            day, cells[0], cells[1], cells[2]
+        );
+    }
+
+    (timings, code)
+}
+
+fn main() -> ExitCode {
+    let command = match parse_args(std::env::args().skip(1)) {
+        Ok(command) => command,
+        Err(e) => {
+            eprintln!("{}\n\n{}", e, USAGE);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    match command {
+        Command::List => {
+            for solution in days::SOLUTIONS {
+                println!("{:>2}  {}", solution.day(), solution.title());
+            }
+
+            ExitCode::SUCCESS
+        }
+        Command::Run { day, part, input } => {
+            let Some(solution) = days::get(day) else {
+                eprintln!("no solution for day {}", day);
+                return ExitCode::FAILURE;
+            };
+
+            let input = match input.load() {
+                Ok(input) => input,
+                Err(e) => {
+                    eprintln!("{}", e);
+                    return ExitCode::FAILURE;
+                }
+            };
+
+            let parts = match part {
+                Some(part) => vec![part],
+                None => solution.parts().to_vec(),
+            };
+
+            run(solution, &parts, &input)
+        }
+        Command::Dot {
+            day,
+            network,
+            plan,
+            input,
+        } => {
+            if day != 16 {
+                eprintln!("no diagram for day {}", day);
+                return ExitCode::FAILURE;
+            }
+
+            let input = match input.load() {
+                Ok(input) => input,
+                Err(e) => {
+                    eprintln!("{}", e);
+                    return ExitCode::FAILURE;
+                }
+            };
+
+            match dot(network, plan, &input) {
+                Ok(dot) => {
+                    print!("{}", dot);
+                    ExitCode::SUCCESS
+                }
+                Err(e) => {
+                    eprintln!("Day 16 diagram failed: {}", e);
+                    ExitCode::FAILURE
+                }
+            }
+        }
+        Command::Bench {
+            days,
+            runs,
+            data,
+            save,
+            baseline,
+            threshold,
+        } => {
+            let solutions = if days.is_empty() {
+                days::SOLUTIONS.to_vec()
+            } else {
+                let mut solutions = vec![];
+                for day in days {
+                    let Some(solution) = days::get(day) else {
+                        eprintln!("no solution for day {}", day);
+                        return ExitCode::FAILURE;
+                    };
+                    solutions.push(solution);
+                }
+                solutions
+            };
+
+            let baseline = match baseline.map(|path| {
+                std::fs::read_to_string(&path)
+                    .map_err(|e| e.to_string())
+                    .and_then(|s| s.parse::<Baseline>())
+                    .map_err(|e| format!("unable to read baseline {}: {}", path, e))
+            }) {
+                Some(Ok(baseline)) => Some(baseline),
+                Some(Err(e)) => {
+                    eprintln!("{}", e);
+                    return ExitCode::FAILURE;
+                }
+                None => None,
+            };
+
+            let (timings, code) = bench(&solutions, runs, &data, baseline.as_ref(), threshold);
+
+            if let Some(path) = save {
+                if let Err(e) = std::fs::write(&path, timings.to_string()) {
+                    eprintln!("unable to save timings to {}: {}", path, e);
+                    return ExitCode::FAILURE;
+                }
+            }
+
+            code
+        }
+    }
+}
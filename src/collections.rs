@@ -0,0 +1,518 @@
+// The smallest chunk `RelocationVec` bothers splitting into, so short sequences aren't chopped up
+// into lots of tiny chunks.
+const MIN_CHUNK_SIZE: usize = 16;
+
+// A sequence where any item can be moved, inserted or removed in O(√n), rather than the O(n) it
+// would take to shift everything along in a `Vec`.
+//
+// Items are kept in chunks of around √n, so finding the right chunk and then the right place
+// inside it both take O(√n). Every item is given an id when it's added, counting from 0, which
+// follows it wherever it's moved, so items can be found again by the order they were added in.
+#[derive(Debug, Clone)]
+pub struct RelocationVec<T> {
+    // Every chunk that's been made, including any emptied out since, in no particular order.
+    chunks: Vec<Vec<(usize, T)>>,
+    // The chunks in use, in sequence order.
+    order: Vec<usize>,
+    // Chunks emptied out by removals, to be used again before any new ones are made.
+    free: Vec<usize>,
+    // Which chunk each item is in, by id, or `None` once it's been removed.
+    chunk_of: Vec<Option<usize>>,
+    len: usize,
+    // Chunks are split when they get to twice this long.
+    chunk_size: usize,
+}
+
+impl<T> RelocationVec<T> {
+    pub fn new() -> RelocationVec<T> {
+        RelocationVec {
+            chunks: vec![],
+            order: vec![],
+            free: vec![],
+            chunk_of: vec![],
+            len: 0,
+            chunk_size: MIN_CHUNK_SIZE,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn get(&self, index: usize) -> Option<&T> {
+        let (o, i) = self.locate(index)?;
+        Some(&self.chunks[self.order[o]][i].1)
+    }
+
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+        let (o, i) = self.locate(index)?;
+        Some(&mut self.chunks[self.order[o]][i].1)
+    }
+
+    // The id of the item at `index`.
+    pub fn id_at(&self, index: usize) -> Option<usize> {
+        let (o, i) = self.locate(index)?;
+        Some(self.chunks[self.order[o]][i].0)
+    }
+
+    pub fn get_by_id(&self, id: usize) -> Option<&T> {
+        let chunk = self.chunk_of.get(id).copied().flatten()?;
+        self.chunks[chunk]
+            .iter()
+            .find(|&&(i, _)| i == id)
+            .map(|(_, item)| item)
+    }
+
+    pub fn get_mut_by_id(&mut self, id: usize) -> Option<&mut T> {
+        let chunk = self.chunk_of.get(id).copied().flatten()?;
+        self.chunks[chunk]
+            .iter_mut()
+            .find(|&&mut (i, _)| i == id)
+            .map(|(_, item)| item)
+    }
+
+    // Where the item is in the sequence now.
+    pub fn index_of(&self, id: usize) -> Option<usize> {
+        let chunk = self.chunk_of.get(id).copied().flatten()?;
+
+        let mut index = 0;
+        for &c in &self.order {
+            if c == chunk {
+                let i = self.chunks[c].iter().position(|&(i, _)| i == id);
+                return Some(index + i.expect("item in its chunk"));
+            }
+            index += self.chunks[c].len();
+        }
+
+        unreachable!("chunk {} holds an item but isn't in use", chunk)
+    }
+
+    // Returns the new item's id.
+    pub fn push(&mut self, item: T) -> usize {
+        self.insert(self.len, item)
+    }
+
+    // Puts the item at `index`, shifting everything after it along. Returns the new item's id.
+    // Panics if `index` is past the end, as `Vec::insert` does.
+    pub fn insert(&mut self, index: usize, item: T) -> usize {
+        let id = self.chunk_of.len();
+        self.chunk_of.push(None);
+        self.place(index, id, item);
+        id
+    }
+
+    // The id isn't given out again.
+    pub fn remove(&mut self, id: usize) -> Option<T> {
+        let chunk = self.chunk_of.get_mut(id)?.take()?;
+        let i = self.chunks[chunk]
+            .iter()
+            .position(|&(i, _)| i == id)
+            .expect("item in its chunk");
+        let (_, item) = self.chunks[chunk].remove(i);
+        self.len -= 1;
+
+        if self.chunks[chunk].is_empty() {
+            self.order.retain(|&c| c != chunk);
+            self.free.push(chunk);
+        }
+
+        Some(item)
+    }
+
+    // Moves the item `offset` places along, treating the sequence as a circle of all the other
+    // items. One that would end up at the very start goes at the end instead, which is the same
+    // place on the circle. Returns the item's new index.
+    pub fn move_by(&mut self, id: usize, offset: i64) -> Option<usize> {
+        let index = self.index_of(id)?;
+        let item = self.remove(id).expect("item just found");
+
+        let others = self.len as i64;
+        let mut target = if others == 0 {
+            0
+        } else {
+            (index as i64 + offset).rem_euclid(others) as usize
+        };
+        if target == 0 && offset != 0 {
+            target = self.len;
+        }
+
+        self.place(target, id, item);
+        Some(target)
+    }
+
+    // Moves the item to `index`, which can't be past the last index. Returns where it was.
+    pub fn move_to(&mut self, id: usize, index: usize) -> Option<usize> {
+        let from = self.index_of(id)?;
+        assert!(
+            index < self.len,
+            "index {} out of bounds for length {}",
+            index,
+            self.len
+        );
+
+        let item = self.remove(id).expect("item just found");
+        self.place(index, id, item);
+        Some(from)
+    }
+
+    // Sorts the chunks back out to around √n each, which moves, inserts and removals slowly
+    // wear down. Happens by itself when there get to be too many chunks.
+    pub fn rebalance(&mut self) {
+        let items = self.take_items();
+        self.chunk_size = self.len.isqrt().max(MIN_CHUNK_SIZE);
+
+        let mut items = items.into_iter().peekable();
+        while items.peek().is_some() {
+            let chunk = items.by_ref().take(self.chunk_size).collect::<Vec<_>>();
+            let c = self.new_chunk(chunk);
+            self.order.push(c);
+        }
+    }
+
+    // In sequence order.
+    pub fn iter(&self) -> impl Iterator<Item = &T> + '_ {
+        self.iter_with_ids().map(|(_, item)| item)
+    }
+
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut T> + '_ {
+        let RelocationVec { chunks, order, .. } = self;
+        let mut chunks = chunks.iter_mut().map(Some).collect::<Vec<_>>();
+
+        order
+            .iter()
+            .flat_map(move |&c| chunks[c].take().expect("each chunk used once"))
+            .map(|(_, item)| item)
+    }
+
+    pub fn iter_with_ids(&self) -> impl Iterator<Item = (usize, &T)> + '_ {
+        self.order
+            .iter()
+            .flat_map(|&c| &self.chunks[c])
+            .map(|(id, item)| (*id, item))
+    }
+
+    // The chunk holding `index` as its place in `order`, and the index within the chunk.
+    fn locate(&self, mut index: usize) -> Option<(usize, usize)> {
+        for (o, &c) in self.order.iter().enumerate() {
+            let n = self.chunks[c].len();
+            if index < n {
+                return Some((o, index));
+            }
+            index -= n;
+        }
+
+        None
+    }
+
+    fn place(&mut self, index: usize, id: usize, item: T) {
+        assert!(
+            index <= self.len,
+            "insertion index {} out of bounds for length {}",
+            index,
+            self.len
+        );
+
+        // The end of the last chunk, rather than a new one, when adding to the end.
+        let (o, i) = match self.locate(index) {
+            Some(found) => found,
+            None if self.order.is_empty() => {
+                let c = self.new_chunk(vec![]);
+                self.order.push(c);
+                (0, 0)
+            }
+            None => {
+                let o = self.order.len() - 1;
+                (o, self.chunks[self.order[o]].len())
+            }
+        };
+
+        let chunk = self.order[o];
+        self.chunks[chunk].insert(i, (id, item));
+        self.chunk_of[id] = Some(chunk);
+        self.len += 1;
+
+        if self.chunks[chunk].len() >= 2 * self.chunk_size {
+            self.split(o);
+
+            if self.order.len() >= 2 * self.chunk_size {
+                self.rebalance();
+            }
+        }
+    }
+
+    // Moves the second half of a chunk out into a new one just after it.
+    fn split(&mut self, o: usize) {
+        let chunk = self.order[o];
+        let half = self.chunks[chunk].len() / 2;
+        let tail = self.chunks[chunk].split_off(half);
+
+        let c = self.new_chunk(tail);
+        self.order.insert(o + 1, c);
+    }
+
+    // Fills an emptied chunk if there is one, or makes a new one. Returns where it is in `chunks`.
+    fn new_chunk(&mut self, items: Vec<(usize, T)>) -> usize {
+        let c = match self.free.pop() {
+            Some(c) => c,
+            None => {
+                self.chunks.push(vec![]);
+                self.chunks.len() - 1
+            }
+        };
+
+        for &(id, _) in &items {
+            self.chunk_of[id] = Some(c);
+        }
+        self.chunks[c] = items;
+        c
+    }
+
+    // Everything in sequence order, leaving no chunks behind.
+    fn take_items(&mut self) -> Vec<(usize, T)> {
+        let mut chunks = std::mem::take(&mut self.chunks);
+        self.free.clear();
+
+        std::mem::take(&mut self.order)
+            .into_iter()
+            .flat_map(|c| std::mem::take(&mut chunks[c]))
+            .collect()
+    }
+}
+
+impl<T> Default for RelocationVec<T> {
+    fn default() -> RelocationVec<T> {
+        RelocationVec::new()
+    }
+}
+
+// Ids count up from 0 in the order the items come in.
+impl<T> FromIterator<T> for RelocationVec<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> RelocationVec<T> {
+        let items = iter.into_iter().enumerate().collect::<Vec<_>>();
+
+        let mut out = RelocationVec::new();
+        out.len = items.len();
+        out.chunk_of = vec![None; items.len()];
+        out.order = vec![0];
+        out.chunks = vec![items];
+        out.rebalance();
+        out
+    }
+}
+
+impl<T> IntoIterator for RelocationVec<T> {
+    type Item = T;
+    type IntoIter = std::iter::Map<std::vec::IntoIter<(usize, T)>, fn((usize, T)) -> T>;
+
+    fn into_iter(mut self) -> Self::IntoIter {
+        self.take_items().into_iter().map(|(_, item)| item)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::VecDeque;
+
+    use proptest::prelude::*;
+
+    use super::*;
+
+    #[test]
+    fn test_relocation_vec() {
+        let numbers = (0..1000).collect::<Vec<_>>();
+        let mut vec = numbers.iter().copied().collect::<RelocationVec<i32>>();
+
+        assert_eq!(vec.len(), 1000);
+        assert_eq!(vec.order.len(), 1000 / 31 + 1);
+        assert_eq!(vec.iter().copied().collect::<Vec<_>>(), numbers);
+
+        assert_eq!(vec.move_by(10, 5), Some(15));
+        assert_eq!(vec.get(15), Some(&10));
+        assert_eq!(vec.get(10), Some(&11));
+        assert_eq!(vec.index_of(10), Some(15));
+        assert_eq!(vec.id_at(14), Some(15));
+
+        // Round the circle, past the start.
+        assert_eq!(vec.move_by(2, -5), Some(996));
+        assert_eq!(vec.move_by(999, 1), Some(1));
+        // The very start is the same place as the very end.
+        let id = vec.id_at(5).unwrap();
+        assert_eq!(vec.move_by(id, -5), Some(999));
+        assert_eq!(vec.id_at(999), Some(id));
+
+        let id = vec.insert(1, -1);
+        assert_eq!(id, 1000);
+        assert_eq!(vec.remove(id), Some(-1));
+        assert_eq!(vec.remove(id), None);
+        assert_eq!(vec.index_of(id), None);
+
+        assert_eq!(vec.into_iter().count(), 1000);
+    }
+
+    #[test]
+    fn test_emptied_chunks_used_again() {
+        let mut vec = (0..1000).collect::<RelocationVec<i32>>();
+        let chunks = vec.chunks.len();
+
+        for _ in 0..10 {
+            for id in vec.iter_with_ids().map(|(id, _)| id).collect::<Vec<_>>() {
+                vec.remove(id);
+            }
+            assert!(vec.is_empty());
+
+            for n in 0..1000 {
+                vec.insert(n as usize / 2, n);
+            }
+            assert_eq!(vec.len(), 1000);
+            assert!(vec.chunks.len() <= chunks, "{} chunks", vec.chunks.len());
+        }
+    }
+
+    #[test]
+    fn test_move_at_the_ends() {
+        // As in the example for 2022's day 20.
+        let mut vec = [1, 2, -3, 3, -2, 0, 4]
+            .into_iter()
+            .collect::<RelocationVec<i64>>();
+
+        for id in 0..vec.len() {
+            let &offset = vec.get_by_id(id).unwrap();
+            vec.move_by(id, offset);
+        }
+
+        assert_eq!(
+            vec.iter().copied().collect::<Vec<_>>(),
+            vec![1, 2, -3, 4, 0, 3, -2]
+        );
+    }
+
+    #[derive(Debug, Clone)]
+    enum Op {
+        Push(i32),
+        Insert(usize, i32),
+        Remove(usize),
+        // Removes this many items in a row from the index, which is enough to empty whole chunks.
+        RemoveRun(usize, usize),
+        MoveBy(usize, i64),
+        MoveTo(usize, usize),
+        Rebalance,
+    }
+
+    fn op() -> impl Strategy<Value = Op> {
+        prop_oneof![
+            any::<i32>().prop_map(Op::Push),
+            (any::<usize>(), any::<i32>()).prop_map(|(i, n)| Op::Insert(i, n)),
+            any::<usize>().prop_map(Op::Remove),
+            (any::<usize>(), 0usize..100).prop_map(|(i, n)| Op::RemoveRun(i, n)),
+            (any::<usize>(), -100i64..100).prop_map(|(id, d)| Op::MoveBy(id, d)),
+            (any::<usize>(), any::<usize>()).prop_map(|(id, i)| Op::MoveTo(id, i)),
+            Just(Op::Rebalance),
+        ]
+    }
+
+    // The same as `move_by`, the slow way.
+    fn model_move_by(model: &mut VecDeque<(usize, i32)>, id: usize, offset: i64) -> Option<usize> {
+        let index = model.iter().position(|&(i, _)| i == id)?;
+        let item = model.remove(index).unwrap();
+
+        let others = model.len() as i64;
+        let mut target = if others == 0 {
+            0
+        } else {
+            (index as i64 + offset).rem_euclid(others) as usize
+        };
+        if target == 0 && offset != 0 {
+            target = model.len();
+        }
+
+        model.insert(target, item);
+        Some(target)
+    }
+
+    proptest! {
+        #[test]
+        fn test_against_vec_deque(
+            initial in prop::collection::vec(any::<i32>(), 0..200),
+            ops in prop::collection::vec(op(), 0..300),
+        ) {
+            let mut vec = initial.iter().copied().collect::<RelocationVec<_>>();
+            let mut model = initial.iter().copied().enumerate().collect::<VecDeque<_>>();
+            let mut next_id = initial.len();
+
+            for op in ops {
+                // Ids that might or might not still be there, and indexes that are in range.
+                let id = |n: usize| n % (next_id + 1);
+                let index = |n: usize, len: usize| n % (len + 1);
+
+                match op {
+                    Op::Push(n) => {
+                        prop_assert_eq!(vec.push(n), next_id);
+                        model.push_back((next_id, n));
+                        next_id += 1;
+                    }
+                    Op::Insert(i, n) => {
+                        let i = index(i, model.len());
+                        prop_assert_eq!(vec.insert(i, n), next_id);
+                        model.insert(i, (next_id, n));
+                        next_id += 1;
+                    }
+                    Op::Remove(id_) => {
+                        let id_ = id(id_);
+                        let expected = model
+                            .iter()
+                            .position(|&(i, _)| i == id_)
+                            .and_then(|i| model.remove(i))
+                            .map(|(_, n)| n);
+                        prop_assert_eq!(vec.remove(id_), expected);
+                    }
+                    Op::RemoveRun(i, n) => {
+                        let i = index(i, model.len());
+                        let n = n.min(model.len() - i);
+                        for (id_, n) in model.drain(i..i + n) {
+                            prop_assert_eq!(vec.remove(id_), Some(n));
+                        }
+                    }
+                    Op::MoveBy(id_, offset) => {
+                        let id_ = id(id_);
+                        prop_assert_eq!(
+                            vec.move_by(id_, offset),
+                            model_move_by(&mut model, id_, offset)
+                        );
+                    }
+                    Op::MoveTo(id_, i) if !model.is_empty() => {
+                        let id_ = id(id_);
+                        let i = i % model.len();
+                        let expected = model.iter().position(|&(j, _)| j == id_);
+                        if let Some(from) = expected {
+                            let item = model.remove(from).unwrap();
+                            model.insert(i, item);
+                        }
+                        prop_assert_eq!(vec.move_to(id_, i), expected);
+                    }
+                    Op::MoveTo(..) => {}
+                    Op::Rebalance => vec.rebalance(),
+                }
+
+                prop_assert_eq!(vec.len(), model.len());
+                // Every chunk is either in use or waiting to be used again, never lost.
+                prop_assert_eq!(vec.chunks.len(), vec.order.len() + vec.free.len());
+                prop_assert!(vec.free.iter().all(|&c| vec.chunks[c].is_empty()));
+                prop_assert!(vec.order.iter().all(|&c| !vec.chunks[c].is_empty()));
+                prop_assert_eq!(
+                    vec.iter_with_ids().map(|(id, &n)| (id, n)).collect::<Vec<_>>(),
+                    model.iter().copied().collect::<Vec<_>>()
+                );
+            }
+
+            for (index, &(id, n)) in model.iter().enumerate() {
+                prop_assert_eq!(vec.index_of(id), Some(index));
+                prop_assert_eq!(vec.get_by_id(id), Some(&n));
+                prop_assert_eq!(vec.get(index), Some(&n));
+            }
+        }
+    }
+}
@@ -0,0 +1,212 @@
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
+use std::hash::Hash;
+
+// Where a sequence of states starts repeating itself: the state after `start + length` steps is
+// the same as the state after `start`, and it goes round the same way from there on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cycle {
+    pub start: usize,
+    pub length: usize,
+}
+
+impl Cycle {
+    // The step before the end of the first time round that's in the same state as step `n`.
+    pub fn reduce(&self, n: usize) -> usize {
+        if n < self.start {
+            n
+        } else {
+            self.start + (n - self.start) % self.length
+        }
+    }
+
+    // Something that changes by the same amount every time round, like a running total, at step
+    // `n`. Needs its value at every step up to `start + length`, the end of the first time round.
+    pub fn extrapolate(&self, values: &[i64], n: usize) -> i64 {
+        if let Some(&value) = values.get(n) {
+            return value;
+        }
+
+        let laps = ((n - self.start) / self.length) as i64;
+        let per_lap = values[self.start + self.length] - values[self.start];
+
+        values[self.reduce(n)] + laps * per_lap
+    }
+}
+
+// Floyd's tortoise and hare. Only keeps a couple of states around at once, but works each one
+// out a few times over, and never returns if the states don't repeat.
+pub fn floyd<S, F>(initial: S, mut step: F) -> Cycle
+where
+    S: PartialEq,
+    F: FnMut(&S) -> S,
+{
+    // Somewhere in the cycle, a multiple of its length from the start.
+    let mut tortoise = step(&initial);
+    let mut hare = step(&tortoise);
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        hare = step(&hare);
+    }
+
+    let mut start = 0;
+    tortoise = initial;
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        start += 1;
+    }
+
+    let mut length = 1;
+    hare = step(&tortoise);
+    while tortoise != hare {
+        hare = step(&hare);
+        length += 1;
+    }
+
+    Cycle { start, length }
+}
+
+// Brent's algorithm: like `floyd`, but works out fewer states along the way.
+pub fn brent<S, F>(initial: S, mut step: F) -> Cycle
+where
+    S: Clone + PartialEq,
+    F: FnMut(&S) -> S,
+{
+    // The hare runs ahead in ever longer stretches until it finds the tortoise waiting for it.
+    let mut power = 1;
+    let mut length = 1;
+    let mut tortoise = initial.clone();
+    let mut hare = step(&initial);
+    while tortoise != hare {
+        if power == length {
+            tortoise = hare.clone();
+            power *= 2;
+            length = 0;
+        }
+        hare = step(&hare);
+        length += 1;
+    }
+
+    // With the hare a whole cycle ahead, they meet where it starts.
+    let mut start = 0;
+    tortoise = initial.clone();
+    hare = initial;
+    for _ in 0..length {
+        hare = step(&hare);
+    }
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        start += 1;
+    }
+
+    Cycle { start, length }
+}
+
+// Looks for the first key that's been seen before, for states that are too big to compare or
+// too slow to work out more than once, but that something smaller can tell apart. Each key has to
+// pin down everything about its state that decides what comes next. Stops when `keys` runs out.
+pub fn find_by_key<K, I>(keys: I) -> Option<Cycle>
+where
+    K: Eq + Hash,
+    I: IntoIterator<Item = K>,
+{
+    let mut seen = HashMap::new();
+
+    for (i, key) in keys.into_iter().enumerate() {
+        match seen.entry(key) {
+            Entry::Occupied(e) => {
+                let start = *e.get();
+                return Some(Cycle {
+                    start,
+                    length: i - start,
+                });
+            }
+            Entry::Vacant(e) => {
+                e.insert(i);
+            }
+        }
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 0, 1, ..., 10, then back to 4.
+    fn step(&x: &u32) -> u32 {
+        if x < 10 {
+            x + 1
+        } else {
+            4
+        }
+    }
+
+    const CYCLE: Cycle = Cycle {
+        start: 4,
+        length: 7,
+    };
+
+    #[test]
+    fn test_detection() {
+        assert_eq!(floyd(0, step), CYCLE);
+        assert_eq!(brent(0, step), CYCLE);
+
+        let keys = std::iter::successors(Some(0), |x| Some(step(x)));
+        assert_eq!(find_by_key(keys), Some(CYCLE));
+        assert_eq!(find_by_key(0..100), None);
+
+        // Straight into the cycle.
+        assert_eq!(
+            floyd(4, step),
+            Cycle {
+                start: 0,
+                length: 7
+            }
+        );
+        assert_eq!(
+            brent(4, step),
+            Cycle {
+                start: 0,
+                length: 7
+            }
+        );
+    }
+
+    #[test]
+    fn test_agrees_on_pseudorandom() {
+        let step = |&x: &u64| (x * x + 1) % 1009;
+
+        for initial in [0, 2, 500] {
+            let keys = std::iter::successors(Some(initial), |x| Some(step(x)));
+
+            assert_eq!(floyd(initial, step), brent(initial, step));
+            assert_eq!(Some(floyd(initial, step)), find_by_key(keys));
+        }
+    }
+
+    #[test]
+    fn test_extrapolate() {
+        assert_eq!(CYCLE.reduce(3), 3);
+        assert_eq!(CYCLE.reduce(11), 4);
+        assert_eq!(CYCLE.reduce(1000), 4 + (1000 - 4) % 7);
+
+        // A running total of the states.
+        let states = std::iter::successors(Some(0), |x| Some(step(x)));
+        let totals = states
+            .scan(0, |total, x| {
+                *total += x as i64;
+                Some(*total)
+            })
+            .take(1001)
+            .collect::<Vec<_>>();
+
+        for n in [0, 5, 11, 12, 100, 1000] {
+            assert_eq!(CYCLE.extrapolate(&totals[..=11], n), totals[n]);
+        }
+    }
+}
@@ -0,0 +1,159 @@
+use std::io::BufRead;
+
+use crate::parser::parse_lines;
+use crate::solution::{Answer, DayError, Solution, SolveError};
+
+enum Input {
+    Number(u64),
+    Blank,
+}
+
+#[derive(Debug)]
+enum Error {
+    InvalidInput,
+}
+
+impl std::error::Error for Error {}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::InvalidInput => f.write_str("invalid input"),
+        }
+    }
+}
+
+impl DayError for Error {
+    fn day(&self) -> u8 {
+        1
+    }
+}
+
+#[derive(Default)]
+struct State {
+    // We need the top 3 values, but leave space for an extra value to be sorted in with them.
+    maxes: [u64; 4],
+    acc: u64,
+}
+
+impl State {
+    fn add(self, n: u64) -> State {
+        let State { maxes, acc } = self;
+        State {
+            maxes,
+            acc: acc + n,
+        }
+    }
+
+    fn next(self) -> State {
+        let State { mut maxes, acc } = self;
+
+        // Always in ascending order. Replace the lowest value and sort again.
+        maxes[0] = acc;
+        maxes.sort();
+
+        State { maxes, acc: 0 }
+    }
+
+    fn finish(self) -> (u64, u64, u64) {
+        // Discard the lowest of the 4, it was just left over from the last iteration.
+        let [_, a, b, c] = self.next().maxes;
+        (a, b, c)
+    }
+}
+
+pub struct Day01;
+
+impl Solution for Day01 {
+    fn day(&self) -> u8 {
+        1
+    }
+
+    fn title(&self) -> &'static str {
+        "Calorie Counting"
+    }
+
+    fn parse(&self, input: &str) -> Result<(), SolveError> {
+        parse_lines(input.as_bytes(), parse).try_for_each(|line| line.map(drop))
+    }
+
+    fn part1(&self, input: &str) -> Result<Answer, SolveError> {
+        part1(input)
+    }
+
+    fn part2(&self, input: &str) -> Result<Answer, SolveError> {
+        part2(input)
+    }
+}
+
+pub fn part1(input: &str) -> Result<Answer, SolveError> {
+    part1_from_reader(input.as_bytes())
+}
+
+pub fn part2(input: &str) -> Result<Answer, SolveError> {
+    part2_from_reader(input.as_bytes())
+}
+
+pub fn part1_from_reader<R: BufRead>(reader: R) -> Result<Answer, SolveError> {
+    let (_, _, max) = top_three(reader)?;
+    Ok(max.into())
+}
+
+pub fn part2_from_reader<R: BufRead>(reader: R) -> Result<Answer, SolveError> {
+    let (a, b, c) = top_three(reader)?;
+    let max = a + b + c;
+    Ok(max.into())
+}
+
+// In ascending order, so the largest total is last.
+fn top_three<R: BufRead>(reader: R) -> Result<(u64, u64, u64), SolveError> {
+    let state =
+        parse_lines(reader, parse).try_fold(State::default(), |state, line| match line? {
+            Input::Number(n) => Ok::<_, SolveError>(state.add(n)),
+            Input::Blank => Ok(state.next()),
+        })?;
+
+    Ok(state.finish())
+}
+
+fn parse(line: &str) -> Result<Input, Error> {
+    if line.is_empty() {
+        Ok(Input::Blank)
+    } else if let Ok(n) = line.parse::<u64>() {
+        Ok(Input::Number(n))
+    } else {
+        Err(Error::InvalidInput)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+        1000\n\
+        2000\n\
+        3000\n\
+        \n\
+        4000\n\
+        \n\
+        5000\n\
+        6000\n\
+        \n\
+        7000\n\
+        8000\n\
+        9000\n\
+        \n\
+        10000\n\
+    ";
+
+    #[test]
+    fn test_example_part1() {
+        assert_eq!(part1(EXAMPLE).unwrap(), Answer::Number(24000));
+    }
+
+    #[test]
+    fn test_example_part2() {
+        assert_eq!(part2(EXAMPLE).unwrap(), Answer::Number(45000));
+    }
+}
@@ -0,0 +1,265 @@
+use std::fmt::Debug;
+use std::io::BufRead;
+use std::ops::Add;
+
+use crate::parser::parse_lines;
+use crate::solution::{Answer, DayError, Solution, SolveError};
+
+#[derive(Debug)]
+enum Error {
+    UnknownShape(String),
+    UnknownOutcome(String),
+    MalformedLine(String),
+}
+
+impl std::error::Error for Error {}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::UnknownShape(s) => write!(f, "invalid shape: {:?}", s),
+            Error::UnknownOutcome(s) => write!(f, "invalid outcome: {:?}", s),
+            Error::MalformedLine(line) => write!(f, "invalid line: {:?}", line),
+        }
+    }
+}
+
+impl DayError for Error {
+    fn day(&self) -> u8 {
+        2
+    }
+}
+
+#[derive(Default)]
+struct Points(u64);
+
+impl Add for Points {
+    type Output = Points;
+
+    fn add(self, other: Points) -> Self::Output {
+        Points(self.0 + other.0)
+    }
+}
+
+enum Shape {
+    Rock,
+    Paper,
+    Scissors,
+}
+
+impl TryFrom<&str> for Shape {
+    type Error = Error;
+
+    fn try_from(s: &str) -> Result<Shape, Error> {
+        use Shape::*;
+
+        match s {
+            "A" => Ok(Rock),
+            "B" => Ok(Paper),
+            "C" => Ok(Scissors),
+            _ => Err(Error::UnknownShape(s.to_owned())),
+        }
+    }
+}
+
+impl Shape {
+    // Part 1 reads the second column as the shape to play, rather than the outcome.
+    fn from_response(s: &str) -> Result<Shape, Error> {
+        use Shape::*;
+
+        match s {
+            "X" => Ok(Rock),
+            "Y" => Ok(Paper),
+            "Z" => Ok(Scissors),
+            _ => Err(Error::UnknownShape(s.to_owned())),
+        }
+    }
+}
+
+impl From<Shape> for Points {
+    fn from(m: Shape) -> Points {
+        use Shape::*;
+
+        Points(match m {
+            Rock => 1,
+            Paper => 2,
+            Scissors => 3,
+        })
+    }
+}
+
+enum Outcome {
+    ElfWin,
+    Draw,
+    MeWin,
+}
+
+impl From<Outcome> for Points {
+    fn from(o: Outcome) -> Points {
+        use Outcome::*;
+
+        Points(match o {
+            ElfWin => 0,
+            Draw => 3,
+            MeWin => 6,
+        })
+    }
+}
+
+impl TryFrom<&str> for Outcome {
+    type Error = Error;
+
+    fn try_from(s: &str) -> Result<Outcome, Error> {
+        use Outcome::*;
+
+        match s {
+            "X" => Ok(ElfWin),
+            "Y" => Ok(Draw),
+            "Z" => Ok(MeWin),
+            _ => Err(Error::UnknownOutcome(s.to_owned())),
+        }
+    }
+}
+
+struct Round {
+    elf: Shape,
+    outcome: Outcome,
+}
+
+impl Round {
+    fn from_shapes(elf: Shape, me: Shape) -> Round {
+        use {Outcome::*, Shape::*};
+
+        let outcome = match (&elf, &me) {
+            (Rock, Scissors) | (Paper, Rock) | (Scissors, Paper) => ElfWin,
+            (Rock, Rock) | (Paper, Paper) | (Scissors, Scissors) => Draw,
+            (Rock, Paper) | (Paper, Scissors) | (Scissors, Rock) => MeWin,
+        };
+
+        Round { elf, outcome }
+    }
+
+    fn my_shape(&self) -> Shape {
+        use {Outcome::*, Shape::*};
+
+        let Round { elf, outcome } = self;
+
+        match (elf, outcome) {
+            (Rock, ElfWin) | (Scissors, Draw) | (Paper, MeWin) => Scissors,
+            (Scissors, ElfWin) | (Paper, Draw) | (Rock, MeWin) => Paper,
+            (Paper, ElfWin) | (Rock, Draw) | (Scissors, MeWin) => Rock,
+        }
+    }
+}
+
+impl From<Round> for Points {
+    fn from(r: Round) -> Points {
+        Points::from(r.my_shape()) + Points::from(r.outcome)
+    }
+}
+
+pub struct Day02;
+
+impl Solution for Day02 {
+    fn day(&self) -> u8 {
+        2
+    }
+
+    fn title(&self) -> &'static str {
+        "Rock Paper Scissors"
+    }
+
+    fn parse(&self, input: &str) -> Result<(), SolveError> {
+        parse_lines(input.as_bytes(), |line: &str| parse_line(line))
+            .try_for_each(|round| round.map(drop))
+    }
+
+    fn part1(&self, input: &str) -> Result<Answer, SolveError> {
+        part1(input)
+    }
+
+    fn part2(&self, input: &str) -> Result<Answer, SolveError> {
+        part2(input)
+    }
+}
+
+pub fn part1(input: &str) -> Result<Answer, SolveError> {
+    part1_from_reader(input.as_bytes())
+}
+
+pub fn part2(input: &str) -> Result<Answer, SolveError> {
+    part2_from_reader(input.as_bytes())
+}
+
+pub fn part1_from_reader<R: BufRead>(reader: R) -> Result<Answer, SolveError> {
+    total_score(parse_lines(reader, |line: &str| parse_line_as_shapes(line)))
+}
+
+pub fn part2_from_reader<R: BufRead>(reader: R) -> Result<Answer, SolveError> {
+    total_score(parse_lines(reader, |line: &str| parse_line(line)))
+}
+
+fn total_score<I>(mut rounds: I) -> Result<Answer, SolveError>
+where
+    I: Iterator<Item = Result<Round, SolveError>>,
+{
+    let score = rounds.try_fold(Points::default(), |score, round| {
+        Ok::<_, SolveError>(score + round?.into())
+    })?;
+    Ok(score.0.into())
+}
+
+// We get a `String` here, but this is a way to be generic across `String` and `&str`.
+fn parse_line<S>(line: S) -> Result<Round, Error>
+where
+    S: AsRef<str> + Debug,
+{
+    match line.as_ref().split_once(' ') {
+        Some((elf_string, outcome_string)) => Ok(Round {
+            elf: elf_string.try_into()?,
+            outcome: outcome_string.try_into()?,
+        }),
+        None => Err(Error::MalformedLine(line.as_ref().to_owned())),
+    }
+}
+
+fn parse_line_as_shapes<S>(line: S) -> Result<Round, Error>
+where
+    S: AsRef<str> + Debug,
+{
+    match line.as_ref().split_once(' ') {
+        Some((elf_string, me_string)) => Ok(Round::from_shapes(
+            elf_string.try_into()?,
+            Shape::from_response(me_string)?,
+        )),
+        None => Err(Error::MalformedLine(line.as_ref().to_owned())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+        A Y\n\
+        B X\n\
+        C Z\n\
+    ";
+
+    #[test]
+    fn test_example_part1() {
+        assert_eq!(part1(EXAMPLE).unwrap(), Answer::Number(15));
+    }
+
+    #[test]
+    fn test_example_part2() {
+        assert_eq!(part2(EXAMPLE).unwrap(), Answer::Number(12));
+    }
+
+    #[test]
+    fn test_invalid_shape() {
+        let e = part2("A Y\nD X\n").unwrap_err();
+        assert_eq!(e.day(), Some(2));
+        assert_eq!(e.to_string(), "line 2: invalid shape: \"D\"");
+    }
+}
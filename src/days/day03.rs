@@ -0,0 +1,157 @@
+use std::collections::HashSet;
+
+use itertools::Itertools;
+
+use crate::solution::{Answer, DayError, Solution, SolveError};
+
+#[derive(Debug)]
+enum Error {
+    InvalidItem(u8),
+}
+
+impl std::error::Error for Error {}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::InvalidItem(b) => write!(f, "invalid item: {:?}", char::from(*b)),
+        }
+    }
+}
+
+impl DayError for Error {
+    fn day(&self) -> u8 {
+        3
+    }
+}
+
+struct Rucksack {
+    first: HashSet<u8>,
+    second: HashSet<u8>,
+}
+
+impl Rucksack {
+    fn common(self) -> Option<u8> {
+        self.first.intersection(&self.second).next().copied()
+    }
+
+    fn all(&self) -> HashSet<u8> {
+        self.first.union(&self.second).copied().collect()
+    }
+
+    fn multi_way_common<I>(sacks: I) -> Option<u8>
+    where
+        I: IntoIterator<Item = Rucksack>,
+    {
+        let mut sacks = sacks.into_iter();
+        let init = sacks.next()?.all();
+
+        sacks
+            .fold(init, |acc, s| {
+                acc.intersection(&s.all()).copied().collect::<HashSet<_>>()
+            })
+            .iter()
+            .next()
+            .copied()
+    }
+}
+
+impl<S> From<S> for Rucksack
+where
+    S: AsRef<str>,
+{
+    fn from(s: S) -> Rucksack {
+        let s = s.as_ref();
+        let (first, second) = s.split_at(s.len() / 2);
+
+        let first = HashSet::from_iter(first.bytes());
+        let second = HashSet::from_iter(second.bytes());
+
+        Rucksack { first, second }
+    }
+}
+
+fn priority(b: u8) -> Result<u64, Error> {
+    let value = if b.is_ascii_lowercase() {
+        b - b'a' + 1
+    } else if b.is_ascii_uppercase() {
+        b - b'A' + 27
+    } else {
+        return Err(Error::InvalidItem(b));
+    };
+
+    Ok(u64::from(value))
+}
+
+pub struct Day03;
+
+impl Solution for Day03 {
+    fn day(&self) -> u8 {
+        3
+    }
+
+    fn title(&self) -> &'static str {
+        "Rucksack Reorganization"
+    }
+
+    fn parse(&self, input: &str) -> Result<(), SolveError> {
+        input.lines().map(Rucksack::from).for_each(drop);
+        Ok(())
+    }
+
+    fn part1(&self, input: &str) -> Result<Answer, SolveError> {
+        part1(input)
+    }
+
+    fn part2(&self, input: &str) -> Result<Answer, SolveError> {
+        part2(input)
+    }
+}
+
+pub fn part1(input: &str) -> Result<Answer, SolveError> {
+    let value: u64 = input
+        .lines()
+        .map(Rucksack::from)
+        .filter_map(Rucksack::common)
+        .map(priority)
+        .sum::<Result<_, _>>()?;
+
+    Ok(value.into())
+}
+
+pub fn part2(input: &str) -> Result<Answer, SolveError> {
+    let value: u64 = input
+        .lines()
+        .map(Rucksack::from)
+        .chunks(3)
+        .into_iter()
+        .filter_map(Rucksack::multi_way_common)
+        .map(priority)
+        .sum::<Result<_, _>>()?;
+
+    Ok(value.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+        vJrwpWtwJgWrhcsFMMfFFhFp\n\
+        jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL\n\
+        PmmdzqPrVvPwwTWBwg\n\
+        wMqvLMZHhHMvwLHjbvcjnnSBnvTQFn\n\
+        ttgJtRGJQctTZtZT\n\
+        CrZsJsPPZsGzwwsLwLmpwMDw\n\
+    ";
+
+    #[test]
+    fn test_example_part1() {
+        assert_eq!(part1(EXAMPLE).unwrap(), Answer::Number(157));
+    }
+
+    #[test]
+    fn test_example_part2() {
+        assert_eq!(part2(EXAMPLE).unwrap(), Answer::Number(70));
+    }
+}
@@ -0,0 +1,117 @@
+use std::io::BufRead;
+
+use nom::{bytes::complete::tag, character::complete::digit1, combinator::map_res, IResult};
+
+use crate::interval::IntervalSet;
+use crate::parser::{nom_line, parse_lines};
+use crate::solution::{Answer, Solution, SolveError};
+
+type Range = std::ops::RangeInclusive<u64>;
+struct Ranges(Range, Range);
+
+impl Ranges {
+    fn containing(&self) -> bool {
+        let Ranges(r0, r1) = self;
+
+        IntervalSet::from(r0.clone()).contains_range(r1)
+            || IntervalSet::from(r1.clone()).contains_range(r0)
+    }
+
+    fn overlapping(&self) -> bool {
+        let Ranges(r0, r1) = self;
+
+        IntervalSet::from(r0.clone()).overlaps(r1)
+    }
+}
+
+fn integer_parser(input: &str) -> IResult<&str, u64> {
+    map_res(digit1, str::parse)(input)
+}
+
+fn range_parser(input: &str) -> IResult<&str, Range> {
+    let (input, a) = integer_parser(input)?;
+    let (input, _) = tag("-")(input)?;
+    let (input, b) = integer_parser(input)?;
+
+    Ok((input, a..=b))
+}
+
+fn line_parser(input: &str) -> IResult<&str, Ranges> {
+    let (input, a) = range_parser(input)?;
+    let (input, _) = tag(",")(input)?;
+    let (input, b) = range_parser(input)?;
+
+    Ok((input, Ranges(a, b)))
+}
+
+pub struct Day04;
+
+impl Solution for Day04 {
+    fn day(&self) -> u8 {
+        4
+    }
+
+    fn title(&self) -> &'static str {
+        "Camp Cleanup"
+    }
+
+    fn parse(&self, input: &str) -> Result<(), SolveError> {
+        parse_lines(input.as_bytes(), nom_line(line_parser)).try_for_each(|r| r.map(drop))
+    }
+
+    fn part1(&self, input: &str) -> Result<Answer, SolveError> {
+        part1(input)
+    }
+
+    fn part2(&self, input: &str) -> Result<Answer, SolveError> {
+        part2(input)
+    }
+}
+
+pub fn part1(input: &str) -> Result<Answer, SolveError> {
+    part1_from_reader(input.as_bytes())
+}
+
+pub fn part2(input: &str) -> Result<Answer, SolveError> {
+    part2_from_reader(input.as_bytes())
+}
+
+pub fn part1_from_reader<R: BufRead>(reader: R) -> Result<Answer, SolveError> {
+    count_pairs(reader, Ranges::containing)
+}
+
+pub fn part2_from_reader<R: BufRead>(reader: R) -> Result<Answer, SolveError> {
+    count_pairs(reader, Ranges::overlapping)
+}
+
+fn count_pairs<R: BufRead>(reader: R, f: fn(&Ranges) -> bool) -> Result<Answer, SolveError> {
+    let n = parse_lines(reader, nom_line(line_parser)).try_fold(0usize, |n, r| {
+        Ok::<_, SolveError>(if f(&r?) { n + 1 } else { n })
+    })?;
+
+    Ok(n.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+        2-4,6-8\n\
+        2-3,4-5\n\
+        5-7,7-9\n\
+        2-8,3-7\n\
+        6-6,4-6\n\
+        2-6,4-8\n\
+    ";
+
+    #[test]
+    fn test_example_part1() {
+        assert_eq!(part1(EXAMPLE).unwrap(), Answer::Number(2));
+    }
+
+    #[test]
+    fn test_example_part2() {
+        assert_eq!(part2(EXAMPLE).unwrap(), Answer::Number(4));
+    }
+}
@@ -1,16 +1,43 @@
 use std::collections::BTreeMap;
-use std::io::Read;
 use std::iter::from_fn;
 use std::str::FromStr;
 
 use nom::branch::alt;
 use nom::bytes::complete::tag;
 use nom::character::complete::{anychar, char, digit1, line_ending, space0, space1};
-use nom::combinator::eof;
+use nom::combinator::{eof, map_res};
 use nom::multi::{many0, many1, separated_list1};
 use nom::sequence::{delimited, pair, preceded, terminated, tuple};
 use nom::{IResult, Parser};
 
+use crate::parser::nom_parse_to_owned;
+use crate::solution::{Answer, DayError, Solution, SolveError};
+
+#[derive(Debug)]
+enum Error {
+    NoSuchStack(u64),
+    NotEnoughCrates { stack: u64, count: u64 },
+}
+
+impl std::error::Error for Error {}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::NoSuchStack(stack) => write!(f, "no stack named {}", stack),
+            Error::NotEnoughCrates { stack, count } => {
+                write!(f, "stack {} has fewer than {} crates to move", stack, count)
+            }
+        }
+    }
+}
+
+impl DayError for Error {
+    fn day(&self) -> u8 {
+        5
+    }
+}
+
 #[derive(Eq, PartialEq, Debug, Clone, Copy)]
 struct Crate(char);
 
@@ -19,20 +46,45 @@ struct Ship {
     stacks: BTreeMap<u64, Vec<Crate>>,
 }
 
+#[derive(Eq, PartialEq, Debug, Clone, Copy)]
+enum CrateMover {
+    // Moves one crate at a time, so a stack of crates ends up reversed.
+    Model9000,
+    // Moves all the crates at once, keeping their order.
+    Model9001,
+}
+
 impl Ship {
-    fn perform(&mut self, m: Move) {
-        let n = usize::try_from(m.count).unwrap();
+    fn perform(&mut self, m: Move, crane: CrateMover) -> Result<(), Error> {
+        if !self.stacks.contains_key(&m.to) {
+            return Err(Error::NoSuchStack(m.to));
+        }
+
+        let from = self
+            .stacks
+            .get_mut(&m.from)
+            .ok_or(Error::NoSuchStack(m.from))?;
+        let n = usize::try_from(m.count)
+            .ok()
+            .filter(|&n| n <= from.len())
+            .ok_or(Error::NotEnoughCrates {
+                stack: m.from,
+                count: m.count,
+            })?;
+
         // Can't mutably take two values (stacks) from the map, so we need to store the moved
         // crates here in the interim.
         let mut v = Vec::with_capacity(n);
-
-        if let Some(from) = self.stacks.get_mut(&m.from) {
-            v.extend(from_fn(|| from.pop()).take(n));
-        }
+        v.extend(from_fn(|| from.pop()).take(n));
 
         if let Some(to) = self.stacks.get_mut(&m.to) {
-            to.extend(v.into_iter().rev());
+            match crane {
+                CrateMover::Model9000 => to.extend(v),
+                CrateMover::Model9001 => to.extend(v.into_iter().rev()),
+            }
         }
+
+        Ok(())
     }
 
     fn tops(&self) -> impl Iterator<Item = char> + '_ {
@@ -47,25 +99,54 @@ struct Move {
     to: u64,
 }
 
-pub fn main() {
-    use parser::parse_input;
+pub struct Day05;
+
+impl Solution for Day05 {
+    fn day(&self) -> u8 {
+        5
+    }
+
+    fn title(&self) -> &'static str {
+        "Supply Stacks"
+    }
+
+    fn parse(&self, input: &str) -> Result<(), SolveError> {
+        nom_parse_to_owned(parser::parse_input, input)?;
+        Ok(())
+    }
+
+    fn part1(&self, input: &str) -> Result<Answer, SolveError> {
+        part1(input)
+    }
+
+    fn part2(&self, input: &str) -> Result<Answer, SolveError> {
+        part2(input)
+    }
+}
+
+pub fn part1(input: &str) -> Result<Answer, SolveError> {
+    rearrange(input, CrateMover::Model9000)
+}
+
+pub fn part2(input: &str) -> Result<Answer, SolveError> {
+    rearrange(input, CrateMover::Model9001)
+}
 
-    let mut input = String::new();
-    std::io::stdin().read_to_string(&mut input).unwrap();
-    let (_blank, (mut ship, moves)) = parse_input(&input).unwrap();
+fn rearrange(input: &str, crane: CrateMover) -> Result<Answer, SolveError> {
+    let (mut ship, moves) = nom_parse_to_owned(parser::parse_input, input)?;
 
     for m in moves {
-        ship.perform(m);
+        ship.perform(m, crane)?;
     }
 
-    println!("{}", ship.tops().collect::<String>());
+    Ok(ship.tops().collect::<String>().into())
 }
 
 mod parser {
     use super::*;
 
     fn base10_u64(input: &str) -> IResult<&str, u64> {
-        digit1.map(|s| u64::from_str(s).unwrap()).parse(input)
+        map_res(digit1, u64::from_str).parse(input)
     }
 
     fn air(input: &str) -> IResult<&str, Option<Crate>> {
@@ -102,7 +183,7 @@ mod parser {
             let i = row_iters.iter_mut().filter_map(|i| i.next()).peekable();
 
             // Build the vectors, unwrapping Option and discarding None.
-            Some(i.filter_map(std::convert::identity).collect::<Vec<_>>())
+            Some(i.flatten().collect::<Vec<_>>())
                 // Empty list means we're finished and should return None from the iterator.
                 .filter(|v| !v.is_empty())
         });
@@ -200,3 +281,30 @@ mod parser {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = concat!(
+        "    [D]    \n",
+        "[N] [C]    \n",
+        "[Z] [M] [P]\n",
+        " 1   2   3 \n",
+        "\n",
+        "move 1 from 2 to 1\n",
+        "move 3 from 1 to 3\n",
+        "move 2 from 2 to 1\n",
+        "move 1 from 1 to 2\n",
+    );
+
+    #[test]
+    fn test_example_part1() {
+        assert_eq!(part1(EXAMPLE).unwrap(), Answer::from("CMZ"));
+    }
+
+    #[test]
+    fn test_example_part2() {
+        assert_eq!(part2(EXAMPLE).unwrap(), Answer::from("MCD"));
+    }
+}
@@ -0,0 +1,68 @@
+use std::collections::BTreeSet;
+
+use crate::solution::{Answer, Solution, SolveError};
+
+pub struct Day06;
+
+impl Solution for Day06 {
+    fn day(&self) -> u8 {
+        6
+    }
+
+    fn title(&self) -> &'static str {
+        "Tuning Trouble"
+    }
+
+    fn parse(&self, _input: &str) -> Result<(), SolveError> {
+        // The datastream is searched as it is.
+        Ok(())
+    }
+
+    fn part1(&self, input: &str) -> Result<Answer, SolveError> {
+        part1(input)
+    }
+
+    fn part2(&self, input: &str) -> Result<Answer, SolveError> {
+        part2(input)
+    }
+}
+
+pub fn part1(input: &str) -> Result<Answer, SolveError> {
+    Ok(find_marker(input, 4)?.into())
+}
+
+pub fn part2(input: &str) -> Result<Answer, SolveError> {
+    Ok(find_marker(input, 14)?.into())
+}
+
+// The number of characters read once the last `n` characters are all different.
+fn find_marker(input: &str, n: usize) -> Result<usize, &'static str> {
+    let position = input
+        .as_bytes()
+        .windows(n)
+        .enumerate()
+        .find(|(_, slice)| slice.iter().copied().collect::<BTreeSet<_>>().len() == slice.len())
+        .ok_or("no marker found")?
+        .0;
+
+    Ok(position + n)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+        mjqjpqmgbljsphdztnvjfqwrcgsmlb\n\
+    ";
+
+    #[test]
+    fn test_example_part1() {
+        assert_eq!(part1(EXAMPLE).unwrap(), Answer::Number(7));
+    }
+
+    #[test]
+    fn test_example_part2() {
+        assert_eq!(part2(EXAMPLE).unwrap(), Answer::Number(19));
+    }
+}
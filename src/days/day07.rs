@@ -1,22 +1,34 @@
 use std::collections::BTreeMap;
-use std::io::Read;
 
 use nom::branch::alt;
 use nom::bytes::complete::tag;
 use nom::character::complete::{digit1, line_ending, not_line_ending, space1};
-use nom::combinator::{eof, value};
+use nom::combinator::{eof, map_res, value};
 use nom::multi::{many1, separated_list0};
 use nom::sequence::{preceded, separated_pair, terminated, tuple};
 use nom::{IResult, Parser};
 
+use crate::parser::ParseError;
+use crate::solution::{Answer, DayError, Solution, SolveError};
+
 #[derive(Debug)]
-struct ExpectedDirGotFile;
+enum Error {
+    ExpectedDirGotFile,
+}
 
-impl std::error::Error for ExpectedDirGotFile {}
+impl std::error::Error for Error {}
 
-impl std::fmt::Display for ExpectedDirGotFile {
+impl std::fmt::Display for Error {
     fn fmt(&self, fmt: &mut std::fmt::Formatter) -> std::fmt::Result {
-        fmt.write_str("expected dir got file")
+        match self {
+            Error::ExpectedDirGotFile => fmt.write_str("expected dir got file"),
+        }
+    }
+}
+
+impl DayError for Error {
+    fn day(&self) -> u8 {
+        7
     }
 }
 
@@ -39,12 +51,12 @@ enum DirEnt<'p> {
 }
 
 impl<'p> DirEnt<'p> {
-    fn get_nested_mut<I>(&mut self, mut path: I) -> Result<&mut Self, ExpectedDirGotFile>
+    fn get_nested_mut<I>(&mut self, mut path: I) -> Result<&mut Self, Error>
     where
         I: Iterator<Item = &'p str>,
     {
         match (self, path.next()) {
-            (DirEnt::File { .. }, _) => Err(ExpectedDirGotFile),
+            (DirEnt::File { .. }, _) => Err(Error::ExpectedDirGotFile),
             (DirEnt::Dir { entries }, Some(name)) => entries
                 .entry(name)
                 .or_insert_with(|| DirEnt::Dir {
@@ -55,12 +67,12 @@ impl<'p> DirEnt<'p> {
         }
     }
 
-    fn multi_insert<I>(&mut self, listing: I) -> Result<(), ExpectedDirGotFile>
+    fn multi_insert<I>(&mut self, listing: I) -> Result<(), Error>
     where
         I: Iterator<Item = LsEntry<'p>>,
     {
         match self {
-            DirEnt::File { .. } => Err(ExpectedDirGotFile),
+            DirEnt::File { .. } => Err(Error::ExpectedDirGotFile),
             DirEnt::Dir { entries } => {
                 entries.extend(listing.map(|e| match e {
                     LsEntry::File { size, name } => (name, DirEnt::File { size }),
@@ -76,7 +88,7 @@ impl<'p> DirEnt<'p> {
         }
     }
 
-    fn visit_recursive_sizes(&self, f: &mut dyn FnMut(usize) -> ()) -> usize {
+    fn visit_recursive_sizes(&self, f: &mut dyn FnMut(usize)) -> usize {
         match self {
             DirEnt::File { size } => *size,
             DirEnt::Dir { entries } => {
@@ -107,11 +119,55 @@ enum Command<'p> {
     Cd { target: CdTarget<'p> },
 }
 
-pub fn main() -> Result<(), Box<dyn std::error::Error>> {
-    let mut buffer = String::new();
-    std::io::stdin().read_to_string(&mut buffer)?;
+pub struct Day07;
+
+impl Solution for Day07 {
+    fn day(&self) -> u8 {
+        7
+    }
+
+    fn title(&self) -> &'static str {
+        "No Space Left On Device"
+    }
+
+    fn parse(&self, input: &str) -> Result<(), SolveError> {
+        parser::command_parser(input).map_err(|e| ParseError::from_nom(input, e))?;
+        Ok(())
+    }
+
+    fn part1(&self, input: &str) -> Result<Answer, SolveError> {
+        part1(input)
+    }
+
+    fn part2(&self, input: &str) -> Result<Answer, SolveError> {
+        part2(input)
+    }
+}
+
+pub fn part1(input: &str) -> Result<Answer, SolveError> {
+    let sizes = directory_sizes(input)?;
+    let total: usize = sizes.into_iter().filter(|n| *n <= 100000).sum();
+
+    Ok(total.into())
+}
+
+pub fn part2(input: &str) -> Result<Answer, SolveError> {
+    let sizes = directory_sizes(input)?;
+    let root_size = sizes.last().ok_or("no directories")?;
+    let target = 30000000 - (70000000 - root_size);
+    let found = sizes
+        .into_iter()
+        .filter(|n| *n > target)
+        .min()
+        .ok_or("no directory is large enough")?;
+
+    Ok(found.into())
+}
+
+// The total size of every directory, with the root directory last.
+fn directory_sizes(input: &str) -> Result<Vec<usize>, SolveError> {
     let (_remaining, commands) =
-        parser::command_parser(&buffer).map_err(|e| e.map_input(str::to_owned))?;
+        parser::command_parser(input).map_err(|e| ParseError::from_nom(input, e))?;
 
     let mut root = DirEnt::Dir {
         entries: BTreeMap::default(),
@@ -145,22 +201,15 @@ pub fn main() -> Result<(), Box<dyn std::error::Error>> {
     root.visit_recursive_sizes(&mut |size| {
         sizes.push(size);
     });
-    let root_size = sizes.iter().rev().next().unwrap();
-    let target = 30000000 - (70000000 - root_size);
-    let found = sizes.into_iter().filter(|n| *n > target).min().unwrap();
-
-    println!("{}", found);
 
-    Ok(())
+    Ok(sizes)
 }
 
 mod parser {
     use super::*;
 
     fn base10_usize(input: &str) -> IResult<&str, usize> {
-        digit1
-            .map(|s| usize::from_str_radix(s, 10).unwrap())
-            .parse(input)
+        map_res(digit1, str::parse).parse(input)
     }
 
     fn raw_dirent_name(input: &str) -> IResult<&str, &str> {
@@ -178,7 +227,7 @@ mod parser {
         value((), alt((line_ending, eof)))
     }
 
-    fn ls_entries(input: &str) -> IResult<&str, Vec<LsEntry>> {
+    fn ls_entries(input: &str) -> IResult<&str, Vec<LsEntry<'_>>> {
         terminated(
             separated_list0(
                 line_ending,
@@ -194,7 +243,7 @@ mod parser {
         .parse(input)
     }
 
-    fn ls_command(input: &str) -> IResult<&str, Command> {
+    fn ls_command(input: &str) -> IResult<&str, Command<'_>> {
         preceded(
             tuple((command_line(tag("ls")), end_of_command())),
             ls_entries,
@@ -203,7 +252,7 @@ mod parser {
         .parse(input)
     }
 
-    fn cd_target(input: &str) -> IResult<&str, CdTarget> {
+    fn cd_target(input: &str) -> IResult<&str, CdTarget<'_>> {
         raw_dirent_name
             .map(|s| match s {
                 "/" => CdTarget::Root,
@@ -213,7 +262,7 @@ mod parser {
             .parse(input)
     }
 
-    fn cd_command(input: &str) -> IResult<&str, Command> {
+    fn cd_command(input: &str) -> IResult<&str, Command<'_>> {
         terminated(
             preceded(tuple((command_line(tag("cd")), space1)), cd_target),
             end_of_command(),
@@ -222,7 +271,7 @@ mod parser {
         .parse(input)
     }
 
-    pub(super) fn command_parser(input: &str) -> IResult<&str, Vec<Command>> {
+    pub(super) fn command_parser(input: &str) -> IResult<&str, Vec<Command<'_>>> {
         terminated(many1(alt((ls_command, cd_command))), eof).parse(input)
     }
 
@@ -276,3 +325,44 @@ mod parser {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+        $ cd /\n\
+        $ ls\n\
+        dir a\n\
+        14848514 b.txt\n\
+        8504156 c.dat\n\
+        dir d\n\
+        $ cd a\n\
+        $ ls\n\
+        dir e\n\
+        29116 f\n\
+        2557 g\n\
+        62596 h.lst\n\
+        $ cd e\n\
+        $ ls\n\
+        584 i\n\
+        $ cd ..\n\
+        $ cd ..\n\
+        $ cd d\n\
+        $ ls\n\
+        4060174 j\n\
+        8033020 d.log\n\
+        5626152 d.ext\n\
+        7214296 k\n\
+    ";
+
+    #[test]
+    fn test_example_part1() {
+        assert_eq!(part1(EXAMPLE).unwrap(), Answer::Number(95437));
+    }
+
+    #[test]
+    fn test_example_part2() {
+        assert_eq!(part2(EXAMPLE).unwrap(), Answer::Number(24933642));
+    }
+}
@@ -0,0 +1,144 @@
+use std::collections::BTreeMap;
+
+use crate::grid::Grid;
+use crate::solution::{Answer, Solution, SolveError};
+
+// Accumulate the most recent tree of each size in a map, use to update the scores.
+fn update_visible(
+    mut acc: BTreeMap<u8, usize>,
+    tree: (usize, &mut (u8, usize)),
+) -> BTreeMap<u8, usize> {
+    let (index, (h, v)) = tree;
+
+    // The max position of a tree the same size or greater is the closest in this direction.
+    let pos = acc.range(*h..u8::MAX).map(|(_k, v)| *v).max().unwrap_or(0);
+
+    // Trees at the edge get zeroed out here.
+    *v *= index - pos;
+
+    acc.insert(*h, index);
+
+    acc
+}
+
+// Track the tallest tree so far from the edge, anything taller than that is visible.
+fn mark_visible(tallest: Option<u8>, tree: &mut (u8, bool)) -> Option<u8> {
+    let (h, visible) = tree;
+
+    match tallest {
+        Some(t) if t >= *h => Some(t),
+        _ => {
+            *visible = true;
+            Some(*h)
+        }
+    }
+}
+
+pub struct Day08;
+
+impl Solution for Day08 {
+    fn day(&self) -> u8 {
+        8
+    }
+
+    fn title(&self) -> &'static str {
+        "Treetop Tree House"
+    }
+
+    fn parse(&self, input: &str) -> Result<(), SolveError> {
+        Grid::parse(input)?;
+        Ok(())
+    }
+
+    fn part1(&self, input: &str) -> Result<Answer, SolveError> {
+        part1(input)
+    }
+
+    fn part2(&self, input: &str) -> Result<Answer, SolveError> {
+        part2(input)
+    }
+}
+
+pub fn part1(input: &str) -> Result<Answer, SolveError> {
+    let mut trees = Grid::parse(input)?.map(|&b| (b, false));
+
+    // Left to right and right to left
+    for y in 0..trees.height() {
+        trees.row_mut(y).iter_mut().fold(None, mark_visible);
+        trees.row_mut(y).iter_mut().rev().fold(None, mark_visible);
+    }
+
+    // Then the same again, top to bottom and bottom to top.
+    for x in 0..trees.width() {
+        trees.column_mut(x).fold(None, mark_visible);
+        trees.column_mut(x).rev().fold(None, mark_visible);
+    }
+
+    let n = trees.cells().iter().filter(|(_, visible)| *visible).count();
+
+    Ok(n.into())
+}
+
+pub fn part2(input: &str) -> Result<Answer, SolveError> {
+    let mut trees = Grid::parse(input)?.map(|&b| (b, 1));
+
+    // Left to right and right to left
+    for y in 0..trees.height() {
+        trees
+            .row_mut(y)
+            .iter_mut()
+            .enumerate()
+            .fold(BTreeMap::default(), update_visible);
+        trees
+            .row_mut(y)
+            .iter_mut()
+            .rev()
+            .enumerate()
+            .fold(BTreeMap::default(), update_visible);
+    }
+
+    // Then the same again, top to bottom and bottom to top.
+    for x in 0..trees.width() {
+        trees
+            .column_mut(x)
+            .enumerate()
+            .fold(BTreeMap::default(), update_visible);
+        trees
+            .column_mut(x)
+            .rev()
+            .enumerate()
+            .fold(BTreeMap::default(), update_visible);
+    }
+
+    let n: usize = trees
+        .cells()
+        .iter()
+        .map(|(_, score)| *score)
+        .max()
+        .ok_or("no trees")?;
+
+    Ok(n.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+        30373\n\
+        25512\n\
+        65332\n\
+        33549\n\
+        35390\n\
+    ";
+
+    #[test]
+    fn test_example_part1() {
+        assert_eq!(part1(EXAMPLE).unwrap(), Answer::Number(21));
+    }
+
+    #[test]
+    fn test_example_part2() {
+        assert_eq!(part2(EXAMPLE).unwrap(), Answer::Number(8));
+    }
+}
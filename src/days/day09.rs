@@ -0,0 +1,175 @@
+use std::collections::HashSet;
+use std::io::BufRead;
+
+use crate::geom::{Dir4, Point2};
+use crate::parser::{nom_line, parse_lines};
+use crate::solution::{Answer, Solution, SolveError};
+
+fn follow(knot: &mut Point2, other: Point2) {
+    let vec = other - *knot;
+
+    if vec.chebyshev() >= 2 {
+        // Ensure to only move one space at a time.
+        *knot += vec.signum();
+    }
+}
+
+#[derive(Eq, PartialEq, Debug)]
+struct Rope {
+    parts: Vec<Point2>,
+}
+
+impl Rope {
+    fn new(parts: usize) -> Rope {
+        assert!(parts >= 2);
+        let parts = vec![Point2::ORIGIN; parts];
+        Rope { parts }
+    }
+
+    fn step(&mut self, direction: Dir4) {
+        let mut iter = self.parts.iter_mut();
+        let mut prev = {
+            let head: &mut Point2 = iter.next().unwrap();
+            *head += direction.vec();
+            *head
+        };
+
+        for part in iter {
+            follow(part, prev);
+            prev = *part;
+        }
+    }
+
+    fn tail(&self) -> Point2 {
+        *self.parts.iter().last().unwrap()
+    }
+}
+
+#[derive(Clone, Copy)]
+struct Move {
+    direction: Dir4,
+    count: u64,
+}
+
+pub struct Day09;
+
+impl Solution for Day09 {
+    fn day(&self) -> u8 {
+        9
+    }
+
+    fn title(&self) -> &'static str {
+        "Rope Bridge"
+    }
+
+    fn parse(&self, input: &str) -> Result<(), SolveError> {
+        parse_lines(input.as_bytes(), nom_line(parser::a_move)).try_for_each(|m| m.map(drop))
+    }
+
+    fn part1(&self, input: &str) -> Result<Answer, SolveError> {
+        part1(input)
+    }
+
+    fn part2(&self, input: &str) -> Result<Answer, SolveError> {
+        part2(input)
+    }
+}
+
+pub fn part1(input: &str) -> Result<Answer, SolveError> {
+    part1_from_reader(input.as_bytes())
+}
+
+pub fn part2(input: &str) -> Result<Answer, SolveError> {
+    part2_from_reader(input.as_bytes())
+}
+
+pub fn part1_from_reader<R: BufRead>(reader: R) -> Result<Answer, SolveError> {
+    Ok(tail_positions(reader, 2)?.into())
+}
+
+pub fn part2_from_reader<R: BufRead>(reader: R) -> Result<Answer, SolveError> {
+    Ok(tail_positions(reader, 10)?.into())
+}
+
+// The number of distinct positions visited by the tail of a rope with this many knots.
+fn tail_positions<R: BufRead>(reader: R, knots: usize) -> Result<usize, SolveError> {
+    let mut rope = Rope::new(knots);
+    let mut visited: HashSet<Point2> = HashSet::default();
+
+    for m in parse_lines(reader, nom_line(parser::a_move)) {
+        let Move { direction, count } = m?;
+
+        for _i in 0..count {
+            rope.step(direction);
+            visited.insert(rope.tail());
+        }
+    }
+
+    Ok(visited.len())
+}
+
+mod parser {
+    use super::*;
+
+    use crate::parser::base10_numeric;
+
+    use nom::branch::alt;
+    use nom::bytes::complete::tag;
+    use nom::character::complete::space1;
+    use nom::sequence::separated_pair;
+    use nom::{IResult, Parser};
+
+    fn direction(input: &str) -> IResult<&str, Dir4> {
+        alt((
+            tag("U").map(|_| Dir4::North),
+            tag("D").map(|_| Dir4::South),
+            tag("L").map(|_| Dir4::West),
+            tag("R").map(|_| Dir4::East),
+        ))
+        .parse(input)
+    }
+
+    pub(super) fn a_move(input: &str) -> IResult<&str, Move> {
+        separated_pair(direction, space1, base10_numeric)
+            .map(|(direction, count)| Move { direction, count })
+            .parse(input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+        R 4\n\
+        U 4\n\
+        L 3\n\
+        D 1\n\
+        R 4\n\
+        D 1\n\
+        L 5\n\
+        R 2\n\
+    ";
+
+    const LARGER_EXAMPLE: &str = "\
+        R 5\n\
+        U 8\n\
+        L 8\n\
+        D 3\n\
+        R 17\n\
+        D 10\n\
+        L 25\n\
+        U 20\n\
+    ";
+
+    #[test]
+    fn test_example_part1() {
+        assert_eq!(part1(EXAMPLE).unwrap(), Answer::Number(13));
+    }
+
+    #[test]
+    fn test_example_part2() {
+        assert_eq!(part2(EXAMPLE).unwrap(), Answer::Number(1));
+        assert_eq!(part2(LARGER_EXAMPLE).unwrap(), Answer::Number(36));
+    }
+}
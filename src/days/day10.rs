@@ -0,0 +1,384 @@
+use std::io::BufRead;
+
+use itertools::Itertools;
+
+use crate::parser::{nom_line, parse_lines};
+use crate::solution::{Answer, Solution, SolveError};
+
+#[derive(Copy, Clone, Debug)]
+enum Instruction {
+    Addx(i64),
+    Noop,
+}
+
+#[derive(Copy, Clone, Debug)]
+struct State(i64);
+
+impl Default for State {
+    fn default() -> State {
+        State(1)
+    }
+}
+
+struct Execution<I>
+where
+    I: Iterator<Item = Instruction>,
+{
+    state: State,
+    instructions: I,
+    ticks: usize,
+    next_state: State,
+}
+
+impl<I> Execution<I>
+where
+    I: Iterator<Item = Instruction>,
+{
+    fn new(i: I) -> Execution<I> {
+        Execution {
+            state: State(1),
+            instructions: i,
+            ticks: 0,
+            next_state: State(1),
+        }
+    }
+}
+
+impl<I> Iterator for Execution<I>
+where
+    I: Iterator<Item = Instruction>,
+{
+    type Item = State;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.ticks > 0 {
+            self.ticks -= 1;
+            Some(self.state)
+        } else {
+            self.state = self.next_state;
+
+            match self.instructions.next() {
+                Some(Instruction::Noop) => {
+                    self.ticks = 0;
+                    Some(self.state)
+                }
+                Some(Instruction::Addx(n)) => {
+                    self.next_state = State(self.state.0 + n);
+                    self.ticks = 1;
+                    Some(self.state)
+                }
+                None => None,
+            }
+        }
+    }
+}
+
+pub struct Day10;
+
+impl Solution for Day10 {
+    fn day(&self) -> u8 {
+        10
+    }
+
+    fn title(&self) -> &'static str {
+        "Cathode-Ray Tube"
+    }
+
+    fn parse(&self, input: &str) -> Result<(), SolveError> {
+        instructions(input.as_bytes()).try_for_each(|i| i.map(drop))
+    }
+
+    fn part1(&self, input: &str) -> Result<Answer, SolveError> {
+        part1(input)
+    }
+
+    fn part2(&self, input: &str) -> Result<Answer, SolveError> {
+        part2(input)
+    }
+}
+
+pub fn part1(input: &str) -> Result<Answer, SolveError> {
+    part1_from_reader(input.as_bytes())
+}
+
+pub fn part2(input: &str) -> Result<Answer, SolveError> {
+    part2_from_reader(input.as_bytes())
+}
+
+pub fn part1_from_reader<R: BufRead>(reader: R) -> Result<Answer, SolveError> {
+    // The execution yields the state *during* each cycle, starting from cycle 1.
+    let strength: i64 = itertools::process_results(instructions(reader), |mut instructions| {
+        let strength = Execution::new(instructions.by_ref())
+            .zip(1..)
+            .skip(19)
+            .step_by(40)
+            .take(6)
+            .map(|(State(x), cycle)| x * cycle)
+            .sum();
+
+        // The rest of the input isn't needed, but it still has to be valid.
+        instructions.for_each(drop);
+
+        strength
+    })?;
+
+    Ok(strength.into())
+}
+
+pub fn part2_from_reader<R: BufRead>(reader: R) -> Result<Answer, SolveError> {
+    let rows = itertools::process_results(instructions(reader), |instructions| {
+        let iter = Execution::new(instructions)
+            .zip((0..=39).cycle())
+            .chunks(40);
+
+        let mut rows = vec![];
+        for states in &iter {
+            let mut row = String::new();
+            for (state, pos) in states {
+                let ch = if (pos as i64 - state.0).abs() < 2 {
+                    '#'
+                } else {
+                    '.'
+                };
+
+                row.push(ch);
+            }
+            rows.push(row);
+        }
+
+        rows
+    })?;
+
+    Ok(rows.join("\n").into())
+}
+
+fn instructions<R: BufRead>(reader: R) -> impl Iterator<Item = Result<Instruction, SolveError>> {
+    parse_lines(reader, nom_line(parser::instruction))
+}
+
+mod parser {
+    use super::*;
+
+    use crate::parser::base10_numeric;
+
+    use nom::branch::alt;
+    use nom::bytes::complete::tag;
+    use nom::character::complete::space1;
+    use nom::sequence::{preceded, tuple};
+    use nom::{IResult, Parser};
+
+    fn addx_instruction(input: &str) -> IResult<&str, Instruction> {
+        preceded(tuple((tag("addx"), space1)), base10_numeric)
+            .map(Instruction::Addx)
+            .parse(input)
+    }
+
+    fn noop_instruction(input: &str) -> IResult<&str, Instruction> {
+        tag("noop").map(|_| Instruction::Noop).parse(input)
+    }
+
+    pub(super) fn instruction(input: &str) -> IResult<&str, Instruction> {
+        alt((addx_instruction, noop_instruction)).parse(input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_example() {
+        crate::parser::base10_numeric::<i64>("-5").unwrap();
+        crate::parser::base10_numeric::<i64>("3").unwrap();
+        let input = "\
+            noop\n\
+            addx 3\n\
+            addx -5\n\
+        ";
+
+        let instructions = instructions(input.as_bytes()).map(Result::unwrap);
+
+        let execution = Execution::new(instructions);
+        let steps = execution.map(|State(n)| n).collect::<Vec<i64>>();
+
+        assert_eq!(steps, vec![1, 1, 1, 4, 4]);
+    }
+
+    const EXAMPLE_PROGRAM: &str = "\
+        addx 15\n\
+        addx -11\n\
+        addx 6\n\
+        addx -3\n\
+        addx 5\n\
+        addx -1\n\
+        addx -8\n\
+        addx 13\n\
+        addx 4\n\
+        noop\n\
+        addx -1\n\
+        addx 5\n\
+        addx -1\n\
+        addx 5\n\
+        addx -1\n\
+        addx 5\n\
+        addx -1\n\
+        addx 5\n\
+        addx -1\n\
+        addx -35\n\
+        addx 1\n\
+        addx 24\n\
+        addx -19\n\
+        addx 1\n\
+        addx 16\n\
+        addx -11\n\
+        noop\n\
+        noop\n\
+        addx 21\n\
+        addx -15\n\
+        noop\n\
+        noop\n\
+        addx -3\n\
+        addx 9\n\
+        addx 1\n\
+        addx -3\n\
+        addx 8\n\
+        addx 1\n\
+        addx 5\n\
+        noop\n\
+        noop\n\
+        noop\n\
+        noop\n\
+        noop\n\
+        addx -36\n\
+        noop\n\
+        addx 1\n\
+        addx 7\n\
+        noop\n\
+        noop\n\
+        noop\n\
+        addx 2\n\
+        addx 6\n\
+        noop\n\
+        noop\n\
+        noop\n\
+        noop\n\
+        noop\n\
+        addx 1\n\
+        noop\n\
+        noop\n\
+        addx 7\n\
+        addx 1\n\
+        noop\n\
+        addx -13\n\
+        addx 13\n\
+        addx 7\n\
+        noop\n\
+        addx 1\n\
+        addx -33\n\
+        noop\n\
+        noop\n\
+        noop\n\
+        addx 2\n\
+        noop\n\
+        noop\n\
+        noop\n\
+        addx 8\n\
+        noop\n\
+        addx -1\n\
+        addx 2\n\
+        addx 1\n\
+        noop\n\
+        addx 17\n\
+        addx -9\n\
+        addx 1\n\
+        addx 1\n\
+        addx -3\n\
+        addx 11\n\
+        noop\n\
+        noop\n\
+        addx 1\n\
+        noop\n\
+        addx 1\n\
+        noop\n\
+        noop\n\
+        addx -13\n\
+        addx -19\n\
+        addx 1\n\
+        addx 3\n\
+        addx 26\n\
+        addx -30\n\
+        addx 12\n\
+        addx -1\n\
+        addx 3\n\
+        addx 1\n\
+        noop\n\
+        noop\n\
+        noop\n\
+        addx -9\n\
+        addx 18\n\
+        addx 1\n\
+        addx 2\n\
+        noop\n\
+        noop\n\
+        addx 9\n\
+        noop\n\
+        noop\n\
+        noop\n\
+        addx -1\n\
+        addx 2\n\
+        addx -37\n\
+        addx 1\n\
+        addx 3\n\
+        noop\n\
+        addx 15\n\
+        addx -21\n\
+        addx 22\n\
+        addx -6\n\
+        addx 1\n\
+        noop\n\
+        addx 2\n\
+        addx 1\n\
+        noop\n\
+        addx -10\n\
+        noop\n\
+        noop\n\
+        addx 20\n\
+        addx 1\n\
+        addx 2\n\
+        addx 2\n\
+        addx -6\n\
+        addx -11\n\
+        noop\n\
+        noop\n\
+        noop\n\
+    ";
+
+    #[test]
+    fn test_example_part1() {
+        assert_eq!(part1(EXAMPLE_PROGRAM).unwrap(), Answer::Number(13140));
+
+        // Part 1 is done by cycle 220, long before the end of the program, but the rest of it
+        // is still checked.
+        let input = format!("{}\nsubx 1", EXAMPLE_PROGRAM);
+        let Err(SolveError::Parse(e)) = part1(&input) else {
+            panic!("expected a parse error");
+        };
+        assert_eq!(e.line, EXAMPLE_PROGRAM.lines().count() + 1);
+    }
+
+    #[test]
+    fn test_example_part2() {
+        assert_eq!(
+            part2(EXAMPLE_PROGRAM).unwrap(),
+            Answer::from(
+                "\
+                ##..##..##..##..##..##..##..##..##..##..\n\
+                ###...###...###...###...###...###...###.\n\
+                ####....####....####....####....####....\n\
+                #####.....#####.....#####.....#####.....\n\
+                ######......######......######......####\n\
+                #######.......#######.......#######....."
+            )
+        );
+    }
+}
@@ -1,6 +1,30 @@
 use std::collections::BTreeMap;
 
-use aoc::parser::read_from_stdin_and_parse;
+use crate::parser::nom_parse_to_owned;
+use crate::solution::{Answer, DayError, Solution, SolveError};
+
+#[derive(Debug)]
+enum Error {
+    NoSuchMonkey { thrower: u64, target: u64 },
+}
+
+impl std::error::Error for Error {}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::NoSuchMonkey { thrower, target } => {
+                write!(f, "monkey {} throws to missing monkey {}", thrower, target)
+            }
+        }
+    }
+}
+
+impl DayError for Error {
+    fn day(&self) -> u8 {
+        11
+    }
+}
 
 #[derive(Debug, Eq, PartialEq, Copy, Clone)]
 struct Item {
@@ -8,13 +32,24 @@ struct Item {
 }
 
 impl Item {
-    fn relieve(self, modulus: u64) -> Item {
-        Item {
-            worry: self.worry % modulus,
-        }
+    fn relieve(self, relief: Relief) -> Item {
+        let worry = match relief {
+            Relief::Divide(n) => self.worry / n,
+            Relief::Modulo(n) => self.worry % n,
+        };
+
+        Item { worry }
     }
 }
 
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+enum Relief {
+    // Part 1: worry is divided after each inspection.
+    Divide(u64),
+    // Part 2: worry never goes down, so keep it within the product of all the tests' divisors.
+    Modulo(u64),
+}
+
 #[derive(Debug, Eq, PartialEq)]
 struct Monkey {
     items: Vec<Item>,
@@ -90,7 +125,7 @@ enum Test {
 impl Test {
     fn matches(&self, item: Item) -> bool {
         match self {
-            Test::DivisibleBy(n) => item.worry % n == 0,
+            Test::DivisibleBy(n) => item.worry.is_multiple_of(*n),
         }
     }
 }
@@ -119,7 +154,7 @@ struct Plan {
 }
 
 impl Plan {
-    fn build(monkey: &mut Monkey, modulus: u64) -> Plan {
+    fn build(monkey: &mut Monkey, relief: Relief) -> Plan {
         let Monkey {
             items,
             operation,
@@ -131,7 +166,7 @@ impl Plan {
 
         let items = items
             .drain(..)
-            .map(|i| operation.apply(i).relieve(modulus))
+            .map(|i| operation.apply(i).relieve(relief))
             .collect();
 
         Plan {
@@ -148,9 +183,41 @@ impl Plan {
     }
 }
 
-pub fn main() -> Result<(), Box<dyn std::error::Error>> {
-    let mut monkeys = read_from_stdin_and_parse(parser::parse_input)?;
-    let ids: Vec<MonkeyId> = monkeys.keys().copied().collect();
+pub struct Day11;
+
+impl Solution for Day11 {
+    fn day(&self) -> u8 {
+        11
+    }
+
+    fn title(&self) -> &'static str {
+        "Monkey in the Middle"
+    }
+
+    fn parse(&self, input: &str) -> Result<(), SolveError> {
+        nom_parse_to_owned(parser::parse_input, input)?;
+        Ok(())
+    }
+
+    fn part1(&self, input: &str) -> Result<Answer, SolveError> {
+        part1(input)
+    }
+
+    fn part2(&self, input: &str) -> Result<Answer, SolveError> {
+        part2(input)
+    }
+}
+
+pub fn part1(input: &str) -> Result<Answer, SolveError> {
+    let mut monkeys = nom_parse_to_owned(parser::parse_input, input)?;
+    check_targets(&monkeys)?;
+
+    Ok(monkey_business(&mut monkeys, 20, Relief::Divide(3)).into())
+}
+
+pub fn part2(input: &str) -> Result<Answer, SolveError> {
+    let mut monkeys = nom_parse_to_owned(parser::parse_input, input)?;
+    check_targets(&monkeys)?;
 
     let modulus = monkeys
         .values()
@@ -158,24 +225,50 @@ pub fn main() -> Result<(), Box<dyn std::error::Error>> {
         .map(|Test::DivisibleBy(n)| n)
         .product::<u64>();
 
-    for _i in 0..10_000 {
+    Ok(monkey_business(&mut monkeys, 10_000, Relief::Modulo(modulus)).into())
+}
+
+// Every monkey is looked up by id while the items are thrown around, so make sure they're all
+// there first.
+fn check_targets(monkeys: &BTreeMap<MonkeyId, Monkey>) -> Result<(), Error> {
+    for (&MonkeyId(thrower), monkey) in monkeys {
+        let ThrowAction {
+            true_target,
+            false_target,
+            ..
+        } = monkey.action;
+
+        for MonkeyId(target) in [true_target, false_target] {
+            if !monkeys.contains_key(&MonkeyId(target)) {
+                return Err(Error::NoSuchMonkey { thrower, target });
+            }
+        }
+    }
+
+    Ok(())
+}
+
+// Every round is played out. Even with the worry levels kept down, the monkeys don't get back
+// to an earlier state within 10000 rounds on my input, so `crate::cycle` can't skip any.
+fn monkey_business(monkeys: &mut BTreeMap<MonkeyId, Monkey>, rounds: usize, relief: Relief) -> u64 {
+    let ids: Vec<MonkeyId> = monkeys.keys().copied().collect();
+
+    for _i in 0..rounds {
         for id in ids.iter() {
-            let plan = Plan::build(Monkey::get_mut(&mut monkeys, *id), modulus);
-            plan.execute(&mut monkeys);
+            let plan = Plan::build(Monkey::get_mut(monkeys, *id), relief);
+            plan.execute(monkeys);
         }
     }
 
     let mut values: Vec<u64> = monkeys.values().map(|monkey| monkey.inspections).collect();
     values.sort_by(|a, b| b.cmp(a));
-    println!("{}", values.iter().take(2).product::<u64>());
-
-    Ok(())
+    values.iter().take(2).product::<u64>()
 }
 
 mod parser {
     use super::*;
 
-    use aoc::parser::base10_numeric;
+    use crate::parser::base10_numeric;
 
     use nom::branch::alt;
     use nom::bytes::complete::tag;
@@ -224,7 +317,7 @@ mod parser {
     fn operand(input: &str) -> IResult<&str, Operand> {
         alt((
             tag("old").map(|_| Operand::Old),
-            base10_numeric.map(|n| Operand::Const(n)),
+            base10_numeric.map(Operand::Const),
         ))
         .parse(input)
     }
@@ -425,3 +518,48 @@ mod parser {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = concat!(
+        "Monkey 0:\n",
+        "  Starting items: 79, 98\n",
+        "  Operation: new = old * 19\n",
+        "  Test: divisible by 23\n",
+        "    If true: throw to monkey 2\n",
+        "    If false: throw to monkey 3\n",
+        "\n",
+        "Monkey 1:\n",
+        "  Starting items: 54, 65, 75, 74\n",
+        "  Operation: new = old + 6\n",
+        "  Test: divisible by 19\n",
+        "    If true: throw to monkey 2\n",
+        "    If false: throw to monkey 0\n",
+        "\n",
+        "Monkey 2:\n",
+        "  Starting items: 79, 60, 97\n",
+        "  Operation: new = old * old\n",
+        "  Test: divisible by 13\n",
+        "    If true: throw to monkey 1\n",
+        "    If false: throw to monkey 3\n",
+        "\n",
+        "Monkey 3:\n",
+        "  Starting items: 74\n",
+        "  Operation: new = old + 3\n",
+        "  Test: divisible by 17\n",
+        "    If true: throw to monkey 0\n",
+        "    If false: throw to monkey 1\n",
+    );
+
+    #[test]
+    fn test_example_part1() {
+        assert_eq!(part1(EXAMPLE).unwrap(), Answer::Number(10605));
+    }
+
+    #[test]
+    fn test_example_part2() {
+        assert_eq!(part2(EXAMPLE).unwrap(), Answer::Number(2713310158));
+    }
+}
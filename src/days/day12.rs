@@ -0,0 +1,176 @@
+use crate::grid::{Grid, Pos};
+use crate::search;
+use crate::solution::{Answer, DayError, Solution, SolveError};
+
+#[derive(Debug)]
+enum Error {
+    InvalidSquare(u8),
+    NoStart,
+    NoDestination,
+    NoPath,
+}
+
+impl std::error::Error for Error {}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::InvalidSquare(b) => write!(f, "invalid square: {:?}", char::from(*b)),
+            Error::NoStart => f.write_str("no square marked S"),
+            Error::NoDestination => f.write_str("no square marked E"),
+            Error::NoPath => f.write_str("no path to the destination"),
+        }
+    }
+}
+
+impl DayError for Error {
+    fn day(&self) -> u8 {
+        12
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum StartingPoint {
+    // Only the square marked `S`.
+    Marked,
+    // Any square at the lowest elevation, including `S`.
+    Lowest,
+}
+
+struct Heightmap {
+    values: Grid<u8>,
+    start: StartingPoint,
+}
+
+impl Heightmap {
+    fn new(values: Grid<u8>, start: StartingPoint) -> Result<Heightmap, Error> {
+        if let Some(&b) = values
+            .cells()
+            .iter()
+            .find(|b| !matches!(b, b'a'..=b'z' | b'S' | b'E'))
+        {
+            return Err(Error::InvalidSquare(b));
+        }
+
+        let map = Heightmap { values, start };
+
+        if map.find_values(b'S').next().is_none() {
+            return Err(Error::NoStart);
+        }
+
+        Ok(map)
+    }
+
+    fn get_height(&self, pos: Pos) -> u8 {
+        match self.values[pos] {
+            b'S' => b'a',
+            b'E' => b'z',
+            c => c,
+        }
+    }
+
+    fn find_starts(&self) -> impl Iterator<Item = Pos> + '_ {
+        let lowest = match self.start {
+            StartingPoint::Marked => None,
+            StartingPoint::Lowest => Some(self.find_values(b'a')),
+        };
+
+        self.find_values(b'S').chain(lowest.into_iter().flatten())
+    }
+
+    fn find_end(&self) -> Option<Pos> {
+        self.find_values(b'E').next()
+    }
+
+    fn find_values(&self, value: u8) -> impl Iterator<Item = Pos> + '_ {
+        self.values.find(move |&c| c == value)
+    }
+
+    fn legal_neighbours(&self, pos: Pos) -> impl Iterator<Item = (Pos, u64)> + '_ {
+        self.values
+            .neighbours4(pos)
+            .filter(move |next| self.legal(pos, *next))
+            .map(|next| (next, 1))
+    }
+
+    fn legal(&self, from: Pos, to: Pos) -> bool {
+        self.get_height(from) + 1 >= self.get_height(to)
+    }
+}
+
+pub struct Day12;
+
+impl Solution for Day12 {
+    fn day(&self) -> u8 {
+        12
+    }
+
+    fn title(&self) -> &'static str {
+        "Hill Climbing Algorithm"
+    }
+
+    fn parse(&self, input: &str) -> Result<(), SolveError> {
+        Heightmap::new(Grid::parse(input)?, StartingPoint::Marked)?;
+        Ok(())
+    }
+
+    fn part1(&self, input: &str) -> Result<Answer, SolveError> {
+        part1(input)
+    }
+
+    fn part2(&self, input: &str) -> Result<Answer, SolveError> {
+        part2(input)
+    }
+}
+
+pub fn part1(input: &str) -> Result<Answer, SolveError> {
+    Ok(climb(input, StartingPoint::Marked)?.into())
+}
+
+pub fn part2(input: &str) -> Result<Answer, SolveError> {
+    Ok(climb(input, StartingPoint::Lowest)?.into())
+}
+
+fn climb(input: &str, start: StartingPoint) -> Result<u64, SolveError> {
+    let map = Heightmap::new(Grid::parse(input)?, start)?;
+    let target = map.find_end().ok_or(Error::NoDestination)?;
+
+    let path = search::bfs(
+        &|&pos: &Pos| map.legal_neighbours(pos),
+        map.find_starts(),
+        |&pos| pos == target,
+    )
+    .ok_or(Error::NoPath)?;
+
+    Ok(path.cost)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+        Sabqponm\n\
+        abcryxxl\n\
+        accszExk\n\
+        acctuvwj\n\
+        abdefghi\n\
+    ";
+
+    #[test]
+    fn test_example_part1() {
+        assert_eq!(part1(EXAMPLE).unwrap(), Answer::Number(31));
+    }
+
+    #[test]
+    fn test_example_part2() {
+        assert_eq!(part2(EXAMPLE).unwrap(), Answer::Number(29));
+    }
+
+    #[test]
+    fn test_no_destination() {
+        let e = part1("Sab\nabc\n").unwrap_err();
+        assert_eq!(e.day(), Some(12));
+        assert_eq!(e.to_string(), "no square marked E");
+    }
+}
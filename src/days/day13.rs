@@ -1,6 +1,7 @@
 use std::cmp::Ordering;
 
-use aoc::parser::read_from_stdin_and_parse;
+use crate::parser::nom_parse_to_owned;
+use crate::solution::{Answer, Solution, SolveError};
 
 #[derive(Eq, PartialEq, Debug, Clone)]
 struct Packet {
@@ -32,8 +33,46 @@ impl Ord for PacketData {
     }
 }
 
-pub fn main() {
-    let mut data = read_from_stdin_and_parse(parser::parse_input).unwrap();
+pub struct Day13;
+
+impl Solution for Day13 {
+    fn day(&self) -> u8 {
+        13
+    }
+
+    fn title(&self) -> &'static str {
+        "Distress Signal"
+    }
+
+    fn parse(&self, input: &str) -> Result<(), SolveError> {
+        nom_parse_to_owned(parser::parse_input, input)?;
+        Ok(())
+    }
+
+    fn part1(&self, input: &str) -> Result<Answer, SolveError> {
+        part1(input)
+    }
+
+    fn part2(&self, input: &str) -> Result<Answer, SolveError> {
+        part2(input)
+    }
+}
+
+pub fn part1(input: &str) -> Result<Answer, SolveError> {
+    let data = nom_parse_to_owned(parser::parse_input, input)?;
+
+    // Packets come in pairs, and the puzzle counts the pairs from 1.
+    let n: usize = data
+        .chunks(2)
+        .zip(1..)
+        .filter(|(pair, _)| matches!(pair, [left, right] if left.data < right.data))
+        .map(|(_, i)| i)
+        .sum();
+    Ok(n.into())
+}
+
+pub fn part2(input: &str) -> Result<Answer, SolveError> {
+    let mut data = nom_parse_to_owned(parser::parse_input, input)?;
 
     let divider1 = Packet {
         data: List(vec![List(vec![Integer(2)])]),
@@ -49,16 +88,15 @@ pub fn main() {
     let first = data.iter().position(|p| p == &divider1);
     let second = data.iter().position(|p| p == &divider2);
 
-    if let Some((i, j)) = first.zip(second) {
-        let n = (i + 1) * (j + 1);
-        println!("{}", n);
-    }
+    let (i, j) = first.zip(second).ok_or("divider packets went missing")?;
+    let n = (i + 1) * (j + 1);
+    Ok(n.into())
 }
 
 mod parser {
     use super::*;
 
-    use aoc::parser::base10_numeric;
+    use crate::parser::base10_numeric;
 
     use nom::branch::alt;
     use nom::bytes::complete::tag;
@@ -138,3 +176,44 @@ mod parser {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+        [1,1,3,1,1]\n\
+        [1,1,5,1,1]\n\
+        \n\
+        [[1],[2,3,4]]\n\
+        [[1],4]\n\
+        \n\
+        [9]\n\
+        [[8,7,6]]\n\
+        \n\
+        [[4,4],4,4]\n\
+        [[4,4],4,4,4]\n\
+        \n\
+        [7,7,7,7]\n\
+        [7,7,7]\n\
+        \n\
+        []\n\
+        [3]\n\
+        \n\
+        [[[]]]\n\
+        [[]]\n\
+        \n\
+        [1,[2,[3,[4,[5,6,7]]]],8,9]\n\
+        [1,[2,[3,[4,[5,6,0]]]],8,9]\n\
+    ";
+
+    #[test]
+    fn test_example_part1() {
+        assert_eq!(part1(EXAMPLE).unwrap(), Answer::Number(13));
+    }
+
+    #[test]
+    fn test_example_part2() {
+        assert_eq!(part2(EXAMPLE).unwrap(), Answer::Number(140));
+    }
+}
@@ -0,0 +1,361 @@
+use std::ops::RangeInclusive;
+
+use itertools::Itertools;
+
+use crate::geom::{Dir8, Point2};
+use crate::grid::Grid;
+use crate::parser::nom_parse_to_owned;
+use crate::solution::{Answer, DayError, Solution, SolveError};
+
+#[derive(Debug)]
+enum Error {
+    DiagonalLine(Point2, Point2),
+    NoRock,
+    SourceBlocked,
+    OutOfBounds,
+}
+
+impl std::error::Error for Error {}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::DiagonalLine(a, b) => {
+                write!(f, "rock line from {} to {} is not straight", a, b)
+            }
+            Error::NoRock => f.write_str("no rock in the scan"),
+            Error::SourceBlocked => f.write_str("sand blocked the source"),
+            Error::OutOfBounds => f.write_str("sand fell out of bounds"),
+        }
+    }
+}
+
+impl DayError for Error {
+    fn day(&self) -> u8 {
+        14
+    }
+}
+
+#[derive(Default, Debug, Clone, Copy)]
+enum Material {
+    #[default]
+    Air,
+    Rock,
+    Sand,
+}
+
+#[derive(Debug)]
+struct Area {
+    x_bounds: RangeInclusive<i64>,
+    y_bounds: RangeInclusive<i64>,
+
+    grid: Grid<Material>,
+}
+
+impl Area {
+    fn new(x_bounds: RangeInclusive<i64>, y_bounds: RangeInclusive<i64>) -> Area {
+        let grid = Grid::new(
+            x_bounds.clone().count(),
+            y_bounds.clone().count(),
+            Material::default(),
+        );
+
+        Area {
+            x_bounds,
+            y_bounds,
+            grid,
+        }
+    }
+
+    fn contains(&self, pos: Point2) -> bool {
+        self.x_bounds.contains(&pos.x) && self.y_bounds.contains(&pos.y)
+    }
+
+    fn translate(&self, pos: Point2) -> Option<(usize, usize)> {
+        if self.contains(pos) {
+            Some((
+                (pos.x - self.x_bounds.start()) as usize,
+                (pos.y - self.y_bounds.start()) as usize,
+            ))
+        } else {
+            None
+        }
+    }
+
+    fn get_at(&self, pos: Point2) -> Option<Material> {
+        self.translate(pos)
+            .and_then(|pos| self.grid.get(pos))
+            .copied()
+    }
+
+    fn set_at(&mut self, pos: Point2, material: Material) {
+        if let Some(v) = self.translate(pos).and_then(|pos| self.grid.get_mut(pos)) {
+            *v = material;
+        }
+    }
+
+    fn fall_from(&self, pos: Point2) -> LandingSpace {
+        // Straight down if it can, otherwise diagonally to the left, then to the right.
+        for p in [Dir8::South, Dir8::SouthWest, Dir8::SouthEast].map(|d| pos + d.vec()) {
+            match self.get_at(p) {
+                Some(Material::Air) => return self.fall_from(p),
+                None => return LandingSpace::OutOfBounds,
+                _ => {}
+            }
+        }
+
+        LandingSpace::Pos(pos)
+    }
+}
+
+enum RockLine {
+    Horizontal { y: i64, x_start: i64, x_end: i64 },
+
+    Vertical { x: i64, y_start: i64, y_end: i64 },
+}
+
+impl RockLine {
+    fn new(start: Point2, end: Point2) -> Result<RockLine, Error> {
+        if start.x == end.x {
+            Ok(RockLine::Vertical {
+                x: start.x,
+                y_start: start.y,
+                y_end: end.y,
+            })
+        } else if start.y == end.y {
+            Ok(RockLine::Horizontal {
+                y: start.y,
+                x_start: start.x,
+                x_end: end.x,
+            })
+        } else {
+            Err(Error::DiagonalLine(start, end))
+        }
+    }
+
+    fn positions(&self) -> Box<dyn Iterator<Item = Point2>> {
+        match *self {
+            RockLine::Horizontal { y, x_start, x_end } => {
+                Box::new(range(x_start, x_end).map(move |x| Point2::new(x, y)))
+            }
+            RockLine::Vertical { x, y_start, y_end } => {
+                Box::new(range(y_start, y_end).map(move |y| Point2::new(x, y)))
+            }
+        }
+    }
+}
+
+#[derive(Debug)]
+enum LandingSpace {
+    OutOfBounds,
+    Pos(Point2),
+}
+
+fn range(a: i64, b: i64) -> RangeInclusive<i64> {
+    if a < b {
+        a..=b
+    } else {
+        b..=a
+    }
+}
+
+fn to_bounds<'i, I, F>(iter: I, f: F) -> Result<RangeInclusive<i64>, Error>
+where
+    I: IntoIterator<Item = &'i Vec<Point2>> + 'i,
+    F: Fn(Point2) -> i64 + Copy,
+{
+    let (start, end) = iter
+        .into_iter()
+        .flat_map(move |v| v.iter().copied().map(f))
+        .minmax()
+        .into_option()
+        .ok_or(Error::NoRock)?;
+
+    Ok(start..=end)
+}
+
+pub struct Day14;
+
+impl Solution for Day14 {
+    fn day(&self) -> u8 {
+        14
+    }
+
+    fn title(&self) -> &'static str {
+        "Regolith Reservoir"
+    }
+
+    fn parse(&self, input: &str) -> Result<(), SolveError> {
+        nom_parse_to_owned(parser::parse_input, input)?;
+        Ok(())
+    }
+
+    fn part1(&self, input: &str) -> Result<Answer, SolveError> {
+        part1(input)
+    }
+
+    fn part2(&self, input: &str) -> Result<Answer, SolveError> {
+        part2(input)
+    }
+}
+
+pub fn part1(input: &str) -> Result<Answer, SolveError> {
+    let data = nom_parse_to_owned(parser::parse_input, input)?;
+
+    // Without a floor, the sand flows into the abyss as soon as it leaves the area spanned by the
+    // rock, so there is no need to leave any room around it.
+    let x_bounds = to_bounds(&data, |p| p.x)?;
+    let y_bounds = to_bounds(&data, |p| p.y)?;
+    let y_bounds = 0..=*y_bounds.end();
+
+    let mut area = Area::new(x_bounds, y_bounds);
+    fill_rock(&mut area, data, None)?;
+
+    let origin = Point2::new(500, 0);
+
+    for i in 0.. {
+        match area.fall_from(origin) {
+            LandingSpace::Pos(pos) if pos == origin => return Err(Error::SourceBlocked.into()),
+            LandingSpace::Pos(pos) => area.set_at(pos, Material::Sand),
+            LandingSpace::OutOfBounds => return Ok(i.into()),
+        }
+    }
+
+    unreachable!()
+}
+
+pub fn part2(input: &str) -> Result<Answer, SolveError> {
+    let data = nom_parse_to_owned(parser::parse_input, input)?;
+
+    let x_bounds = to_bounds(&data, |p| p.x)?;
+    let y_bounds = to_bounds(&data, |p| p.y)?;
+    let y_bounds = 0..=*y_bounds.end() + 2;
+    let x_bounds = (x_bounds.start() - y_bounds.end())..=x_bounds.end() + y_bounds.end();
+
+    let floor = RockLine::Horizontal {
+        y: *y_bounds.end(),
+        x_start: *x_bounds.start(),
+        x_end: *x_bounds.end(),
+    };
+
+    let mut area = Area::new(x_bounds, y_bounds);
+    fill_rock(&mut area, data, Some(floor))?;
+
+    let origin = Point2::new(500, 0);
+
+    for i in 1.. {
+        let landing = area.fall_from(origin);
+        match landing {
+            LandingSpace::Pos(pos) if pos == origin => return Ok(i.into()),
+            LandingSpace::Pos(pos) => area.set_at(pos, Material::Sand),
+            LandingSpace::OutOfBounds => return Err(Error::OutOfBounds.into()),
+        }
+    }
+
+    unreachable!()
+}
+
+fn fill_rock(
+    area: &mut Area,
+    data: Vec<Vec<Point2>>,
+    floor: Option<RockLine>,
+) -> Result<(), Error> {
+    let lines = data
+        .into_iter()
+        .flat_map(|v| {
+            v.into_iter()
+                .tuple_windows()
+                .map(|(a, b)| RockLine::new(a, b))
+        })
+        .chain(floor.map(Ok))
+        .collect::<Result<Vec<RockLine>, _>>()?;
+
+    for line in lines {
+        for pos in line.positions() {
+            area.set_at(pos, Material::Rock);
+        }
+    }
+
+    Ok(())
+}
+
+mod parser {
+    use super::*;
+
+    use crate::parser::base10_numeric;
+
+    use nom::bytes::complete::tag;
+    use nom::character::complete::{line_ending, space0};
+    use nom::combinator::{eof, value};
+    use nom::multi::{many0, many1, separated_list1};
+    use nom::sequence::{separated_pair, terminated, tuple};
+    use nom::{IResult, Parser};
+
+    fn pos(input: &str) -> IResult<&str, Point2> {
+        separated_pair(base10_numeric, tag(","), base10_numeric)
+            .map(|(x, y)| Point2::new(x, y))
+            .parse(input)
+    }
+
+    fn separator(input: &str) -> IResult<&str, ()> {
+        value((), tuple((space0, tag("->"), space0))).parse(input)
+    }
+
+    pub(super) fn parse_input(input: &str) -> IResult<&str, Vec<Vec<Point2>>> {
+        terminated(
+            separated_list1(many1(line_ending), separated_list1(separator, pos)),
+            tuple((many0(line_ending), eof)),
+        )
+        .parse(input)
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[test]
+        fn test_parse_input() {
+            let input = "498,4 -> 498,6 -> 496,6\n\
+                         503,4 -> 502,4 -> 502,9 -> 494,9";
+
+            let (_input, data) = parse_input(input).unwrap();
+
+            assert_eq!(
+                data,
+                vec![
+                    vec![
+                        Point2::new(498, 4),
+                        Point2::new(498, 6),
+                        Point2::new(496, 6),
+                    ],
+                    vec![
+                        Point2::new(503, 4),
+                        Point2::new(502, 4),
+                        Point2::new(502, 9),
+                        Point2::new(494, 9),
+                    ]
+                ]
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+        498,4 -> 498,6 -> 496,6\n\
+        503,4 -> 502,4 -> 502,9 -> 494,9\n\
+    ";
+
+    #[test]
+    fn test_example_part1() {
+        assert_eq!(part1(EXAMPLE).unwrap(), Answer::Number(24));
+    }
+
+    #[test]
+    fn test_example_part2() {
+        assert_eq!(part2(EXAMPLE).unwrap(), Answer::Number(93));
+    }
+}
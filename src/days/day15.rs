@@ -0,0 +1,402 @@
+use std::ops::RangeInclusive;
+
+use itertools::Itertools;
+
+use crate::geom::Point2;
+use crate::interval::IntervalSet;
+use crate::parser::nom_parse_to_owned;
+use crate::solution::{Answer, DayError, Solution, SolveError};
+
+#[derive(Debug)]
+enum Error {
+    NoDistressBeacon,
+    TooManyToCount,
+}
+
+impl std::error::Error for Error {}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::NoDistressBeacon => f.write_str("no distress beacon found"),
+            Error::TooManyToCount => f.write_str("too many positions ruled out to count"),
+        }
+    }
+}
+
+impl DayError for Error {
+    fn day(&self) -> u8 {
+        15
+    }
+}
+
+#[derive(PartialEq, Eq, Debug)]
+struct Sensor {
+    pos: Point2,
+    beacon: Point2,
+}
+
+impl Sensor {
+    // The distance from the sensor to its beacon is the known reach of the sensor.
+    fn reach(&self) -> i64 {
+        self.pos.manhattan(self.beacon)
+    }
+
+    fn covers(&self, pos: Point2) -> bool {
+        self.pos.manhattan(pos) <= self.reach()
+    }
+
+    fn projection(&self, y: i64) -> Option<RangeInclusive<i64>> {
+        let reach = self.reach();
+        // The distance consumed by moving to the target y coordinate.
+        let distance = (y - self.pos.y).abs();
+
+        // We can use the remaining reach to spread left/right from there, as long as
+        // we haven't already exceeded our reach.
+        let x = self.pos.x;
+        let range = (distance - reach + x)..=(reach - distance + x);
+        (!range.is_empty()).then_some(range)
+    }
+}
+
+// Everywhere in row `y` that some sensor can see.
+fn coverage(sensors: &[Sensor], y: i64) -> IntervalSet<i64> {
+    sensors.iter().flat_map(|s| s.projection(y)).collect()
+}
+
+pub struct Day15;
+
+impl Solution for Day15 {
+    fn day(&self) -> u8 {
+        15
+    }
+
+    fn title(&self) -> &'static str {
+        "Beacon Exclusion Zone"
+    }
+
+    fn parse(&self, input: &str) -> Result<(), SolveError> {
+        nom_parse_to_owned(parser::parse_input, input)?;
+        Ok(())
+    }
+
+    fn part1(&self, input: &str) -> Result<Answer, SolveError> {
+        part1(input)
+    }
+
+    fn part2(&self, input: &str) -> Result<Answer, SolveError> {
+        part2(input)
+    }
+}
+
+pub fn part1(input: &str) -> Result<Answer, SolveError> {
+    Ok(BeaconSearch::PUZZLE.ruled_out(input)?.into())
+}
+
+pub fn part2(input: &str) -> Result<Answer, SolveError> {
+    let beacon = BeaconSearch::PUZZLE.distress_beacon(input)?;
+
+    Ok(tuning_frequency(beacon).into())
+}
+
+// How to look for the distress beacon in part 2.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Strategy {
+    // Looks for a gap in what the sensors can see a row at a time, so it takes as long as there
+    // are rows.
+    RowScan,
+    // Only looks where the edges of the sensors' ranges cross, so the size of the area makes no
+    // difference.
+    #[default]
+    Crossings,
+}
+
+// The parts of the puzzle that aren't in the input, which are different for the example.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BeaconSearch {
+    // The row to count the positions a beacon can't be in for part 1.
+    pub row: i64,
+    // Part 2's distress beacon is somewhere from 0 to this, in both directions.
+    pub bounds: i64,
+    pub strategy: Strategy,
+}
+
+impl BeaconSearch {
+    pub const PUZZLE: BeaconSearch = BeaconSearch {
+        row: 2000000,
+        bounds: 4000000,
+        strategy: Strategy::Crossings,
+    };
+
+    pub const EXAMPLE: BeaconSearch = BeaconSearch {
+        row: 10,
+        bounds: 20,
+        strategy: Strategy::Crossings,
+    };
+
+    pub fn new(row: i64, bounds: i64) -> BeaconSearch {
+        BeaconSearch {
+            row,
+            bounds,
+            strategy: Strategy::default(),
+        }
+    }
+
+    pub fn with_strategy(self, strategy: Strategy) -> BeaconSearch {
+        BeaconSearch { strategy, ..self }
+    }
+
+    // The number of positions in the row that can't contain a beacon.
+    pub fn ruled_out(&self, input: &str) -> Result<i64, SolveError> {
+        let sensors = nom_parse_to_owned(parser::parse_input, input)?;
+
+        Ok(ruled_out(&sensors, self.row)?)
+    }
+
+    // The one position within bounds that no sensor can see.
+    pub fn distress_beacon(&self, input: &str) -> Result<Point2, SolveError> {
+        let sensors = nom_parse_to_owned(parser::parse_input, input)?;
+
+        let beacon = match self.strategy {
+            Strategy::RowScan => scan_rows(&sensors, self.bounds),
+            Strategy::Crossings => check_crossings(&sensors, self.bounds),
+        };
+
+        Ok(beacon.ok_or(Error::NoDistressBeacon)?)
+    }
+}
+
+// The same whatever the bounds, even for the example.
+pub fn tuning_frequency(beacon: Point2) -> i64 {
+    beacon.x * 4000000 + beacon.y
+}
+
+// The number of positions in `row` that can't contain a beacon.
+fn ruled_out(sensors: &[Sensor], row: i64) -> Result<i64, Error> {
+    let coverage = coverage(sensors, row);
+
+    // Positions where a beacon is already known can't be ruled out.
+    let beacons = sensors
+        .iter()
+        .map(|s| s.beacon)
+        .filter(|b| b.y == row && coverage.contains(b.x))
+        .unique()
+        .count() as i64;
+
+    Ok(coverage.len().ok_or(Error::TooManyToCount)? - beacons)
+}
+
+// Search 0..=bounds in both directions, a row at a time.
+fn scan_rows(sensors: &[Sensor], bounds: i64) -> Option<Point2> {
+    // The problem promises only one gap in the whole area, so this takes the first one it finds.
+    (0..=bounds).find_map(|y| {
+        let gap = coverage(sensors, y).gaps(0..=bounds).next()?;
+        Some(Point2::new(*gap.start(), y))
+    })
+}
+
+// With only one position left uncovered, every position next to it is covered, so it has to be
+// hemmed in by the edges of the sensors' ranges, or by the edges of the area. Either way, it's
+// somewhere two of those edges cross.
+fn check_crossings(sensors: &[Sensor], bounds: i64) -> Option<Point2> {
+    // Turned 45°, the edges just out of each sensor's reach are lines where x + y or x - y stays
+    // the same.
+    let (mut sums, mut diffs) = (vec![], vec![]);
+    for s in sensors {
+        let (sum, diff, reach) = (s.pos.x + s.pos.y, s.pos.x - s.pos.y, s.reach() + 1);
+        sums.extend([sum - reach, sum + reach]);
+        diffs.extend([diff - reach, diff + reach]);
+    }
+    let (sums, diffs) = (&sums, &diffs);
+
+    // Lines only cross on a whole position when their sum and difference are both even or odd.
+    let crossings = sums
+        .iter()
+        .cartesian_product(diffs)
+        .filter(|&(a, b)| (a + b) % 2 == 0)
+        .map(|(a, b)| Point2::new((a + b) / 2, (a - b) / 2));
+
+    let edges = [0, bounds].into_iter().flat_map(|c| {
+        let sums = sums
+            .iter()
+            .flat_map(move |&a| [Point2::new(c, a - c), Point2::new(a - c, c)]);
+        let diffs = diffs
+            .iter()
+            .flat_map(move |&b| [Point2::new(c, c - b), Point2::new(b + c, c)]);
+        sums.chain(diffs)
+    });
+
+    let corners = [0, bounds]
+        .into_iter()
+        .cartesian_product([0, bounds])
+        .map(|(x, y)| Point2::new(x, y));
+
+    let in_bounds = |p: &Point2| (0..=bounds).contains(&p.x) && (0..=bounds).contains(&p.y);
+
+    crossings
+        .chain(edges)
+        .chain(corners)
+        .filter(in_bounds)
+        .find(|&p| !sensors.iter().any(|s| s.covers(p)))
+}
+
+mod parser {
+    use super::*;
+
+    use crate::parser::base10_numeric;
+
+    use nom::bytes::complete::tag;
+    use nom::character::complete::{line_ending, space0};
+    use nom::combinator::eof;
+    use nom::multi::{many0, many1, separated_list1};
+    use nom::sequence::{preceded, separated_pair, terminated, tuple};
+    use nom::{IResult, Parser};
+
+    fn pos(input: &str) -> IResult<&str, Point2> {
+        separated_pair(
+            preceded(tag("x="), base10_numeric),
+            tuple((space0, tag(","), space0)),
+            preceded(tag("y="), base10_numeric),
+        )
+        .map(|(x, y)| Point2::new(x, y))
+        .parse(input)
+    }
+
+    fn sensor(input: &str) -> IResult<&str, Sensor> {
+        tuple((
+            preceded(tag("Sensor at "), pos),
+            preceded(tag(": closest beacon is at "), pos),
+        ))
+        .map(|(pos, beacon)| Sensor { pos, beacon })
+        .parse(input)
+    }
+
+    pub(super) fn parse_input(input: &str) -> IResult<&str, Vec<Sensor>> {
+        terminated(
+            separated_list1(many1(line_ending), sensor),
+            tuple((many0(line_ending), eof)),
+        )
+        .parse(input)
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[test]
+        fn test_parse_input() {
+            let input = "\
+                Sensor at x=2, y=18: closest beacon is at x=-2, y=15\n\
+                Sensor at x=9, y=16: closest beacon is at x=10, y=16\n\
+                Sensor at x=13, y=2: closest beacon is at x=15, y=3\n";
+
+            let (_, sensors) = parse_input(input).unwrap();
+
+            assert_eq!(
+                sensors,
+                vec![
+                    Sensor {
+                        pos: Point2::new(2, 18),
+                        beacon: Point2::new(-2, 15)
+                    },
+                    Sensor {
+                        pos: Point2::new(9, 16),
+                        beacon: Point2::new(10, 16)
+                    },
+                    Sensor {
+                        pos: Point2::new(13, 2),
+                        beacon: Point2::new(15, 3)
+                    },
+                ]
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+        Sensor at x=2, y=18: closest beacon is at x=-2, y=15\n\
+        Sensor at x=9, y=16: closest beacon is at x=10, y=16\n\
+        Sensor at x=13, y=2: closest beacon is at x=15, y=3\n\
+        Sensor at x=12, y=14: closest beacon is at x=10, y=16\n\
+        Sensor at x=10, y=20: closest beacon is at x=10, y=16\n\
+        Sensor at x=14, y=17: closest beacon is at x=10, y=16\n\
+        Sensor at x=8, y=7: closest beacon is at x=2, y=10\n\
+        Sensor at x=2, y=0: closest beacon is at x=2, y=10\n\
+        Sensor at x=0, y=11: closest beacon is at x=2, y=10\n\
+        Sensor at x=20, y=14: closest beacon is at x=25, y=17\n\
+        Sensor at x=17, y=20: closest beacon is at x=21, y=22\n\
+        Sensor at x=16, y=7: closest beacon is at x=15, y=3\n\
+        Sensor at x=14, y=3: closest beacon is at x=15, y=3\n\
+        Sensor at x=20, y=1: closest beacon is at x=15, y=3\n\
+    ";
+
+    // The example uses a smaller area than the real puzzle.
+    #[test]
+    fn test_example_part1() {
+        assert_eq!(BeaconSearch::EXAMPLE.ruled_out(EXAMPLE).unwrap(), 26);
+    }
+
+    #[test]
+    fn test_example_part2() {
+        let beacon = BeaconSearch::EXAMPLE.distress_beacon(EXAMPLE).unwrap();
+
+        assert_eq!(beacon, Point2::new(14, 11));
+        assert_eq!(tuning_frequency(beacon), 56000011);
+    }
+
+    #[test]
+    fn test_strategies() {
+        for strategy in [Strategy::RowScan, Strategy::Crossings] {
+            let search = BeaconSearch::EXAMPLE.with_strategy(strategy);
+            assert_eq!(
+                search.distress_beacon(EXAMPLE).unwrap(),
+                Point2::new(14, 11)
+            );
+        }
+
+        // One sensor that reaches everywhere but the corner furthest from it.
+        let corner = |bounds: i64| {
+            format!(
+                "Sensor at x={}, y={}: closest beacon is at x={}, y={}\n",
+                bounds,
+                bounds,
+                1 - bounds,
+                bounds
+            )
+        };
+
+        for strategy in [Strategy::RowScan, Strategy::Crossings] {
+            let search = BeaconSearch::new(0, 20).with_strategy(strategy);
+            assert_eq!(
+                search.distress_beacon(&corner(20)).unwrap(),
+                Point2::new(0, 0)
+            );
+        }
+
+        // Far too big to scan.
+        let bounds = 1_000_000_000_000;
+        assert_eq!(
+            BeaconSearch::new(0, bounds)
+                .distress_beacon(&corner(bounds))
+                .unwrap(),
+            Point2::new(0, 0)
+        );
+    }
+
+    #[test]
+    fn test_other_parameters() {
+        let search = BeaconSearch::new(11, 20);
+        assert_eq!(search.ruled_out(EXAMPLE).unwrap(), 28);
+
+        // Too small to reach the gap.
+        let e = BeaconSearch::new(10, 10)
+            .distress_beacon(EXAMPLE)
+            .unwrap_err();
+        assert_eq!(e.day(), Some(15));
+        assert_eq!(e.to_string(), "no distress beacon found");
+    }
+}
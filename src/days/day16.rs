@@ -0,0 +1,1365 @@
+// This is very slow to run but I've already spent way too much time
+// on day 16. It's just a poor graph traversal implementation but I
+// wasn't sure how to short-circuit it further.
+//
+// On my input, part 2 takes around 12 minutes in release mode.
+//
+// The bitmask solver came later, and gets the same answers in a
+// fraction of the time, so that's what the puzzle uses now. The search
+// is still there to check it against.
+
+use id_arena::Id;
+use std::collections::HashMap;
+use std::fmt::Write;
+use std::rc::Rc;
+
+use im::HashSet as ImHashSet;
+
+use crate::parser::nom_parse_to_owned;
+use crate::search;
+use crate::solution::{Answer, DayError, Part, Solution, SolveError};
+use graph::{ValveGraph, ValveNode, ValveNodeConnection};
+
+#[derive(Debug)]
+enum Error {
+    NoStartValve(String),
+    UnknownValve(String),
+    NoActors,
+    TooManyValves(usize),
+    NotInInput(String),
+    NoWay(String, String),
+}
+
+impl std::error::Error for Error {}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::NoStartValve(name) => write!(f, "no valve named {}", name),
+            Error::UnknownValve(name) => write!(f, "tunnel leads to unknown valve {}", name),
+            Error::NoActors => f.write_str("nobody to open the valves"),
+            Error::TooManyValves(n) => write!(
+                f,
+                "{} valves is too many to share out, the most is {}",
+                n, MAX_BITMASK_VALVES
+            ),
+            Error::NotInInput(name) => write!(f, "plan opens valve {}, which isn't there", name),
+            Error::NoWay(from, to) => write!(f, "no way from {} to {}", from, to),
+        }
+    }
+}
+
+impl DayError for Error {
+    fn day(&self) -> u8 {
+        16
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+struct Valve {
+    name: String,
+    flow_rate: u64,
+}
+
+const START_VALVE: &str = "AA";
+
+// The bitmask solver keeps a total for every set of valves, so it runs out of memory long before
+// it runs out of bits.
+const MAX_BITMASK_VALVES: usize = 20;
+
+// Someone opening valves: you, or one of the elephants you've taught.
+#[derive(Debug, Clone, Copy)]
+struct Actor<'a> {
+    // When they're next free to move on, and where they'll be then.
+    free_at: u64,
+    node: &'a ValveNode,
+    // The flow from the valve they're opening, which starts when they're free.
+    opening: u64,
+    // Which actor this is, for the schedule.
+    index: usize,
+}
+
+#[derive(Debug, Clone)]
+struct State<'a> {
+    // Sorted so the first is the next to be free.
+    actors: Vec<Actor<'a>>,
+    flow_rate: u64,
+    released: u64,
+    time_tallied: u64,
+    visited: ImHashSet<Id<ValveNode>>,
+    // When each valve was opened, and who by, latest first. Shared with the states that came
+    // before, as there are far too many states to each have a copy.
+    opened: Option<Rc<Opened>>,
+}
+
+#[derive(Debug)]
+struct Opened {
+    minute: u64,
+    actor: usize,
+    valve: Id<ValveNode>,
+    before: Option<Rc<Opened>>,
+}
+
+impl<'a> State<'a> {
+    fn new(node: &'a ValveNode, actors: usize) -> State<'a> {
+        let actors = (0..actors)
+            .map(|index| Actor {
+                free_at: 0,
+                node,
+                opening: 0,
+                index,
+            })
+            .collect();
+
+        State {
+            actors,
+            flow_rate: 0,
+            released: 0,
+            time_tallied: 0,
+            visited: ImHashSet::unit(node.id),
+            opened: None,
+        }
+    }
+
+    fn can_continue(&self, time_limit: u64) -> bool {
+        self.actors.iter().any(|a| a.free_at < time_limit)
+    }
+
+    fn is_valid(&self, time_limit: u64) -> bool {
+        self.actors.iter().all(|a| a.free_at <= time_limit) && self.time_tallied == time_limit
+    }
+
+    // Compute the final total for the `State`, assuming it doesn't move again.
+    fn finish(&self, time_limit: u64) -> State<'a> {
+        let mut state = self.clone();
+
+        // Accrue for visited valves that haven't been added yet.
+        for actor in state.actors.iter_mut().filter(|a| a.free_at < time_limit) {
+            let t = std::mem::replace(&mut actor.free_at, time_limit);
+            state.released += (t - state.time_tallied) * state.flow_rate;
+            state.flow_rate += std::mem::take(&mut actor.opening);
+            state.time_tallied = t;
+        }
+
+        state.released += (time_limit - state.time_tallied) * state.flow_rate;
+        state.time_tallied = time_limit;
+
+        state
+    }
+
+    // Counts up to when the first actor is free, and the valve they were opening starts flowing.
+    fn tally(&mut self) {
+        let actor = &mut self.actors[0];
+
+        let release_steps = actor.free_at - self.time_tallied;
+        self.released += release_steps * self.flow_rate;
+        self.time_tallied += release_steps;
+        self.flow_rate += std::mem::take(&mut actor.opening);
+    }
+
+    // The first actor stops where they are, leaving the rest of the valves to everyone else.
+    fn retire(&self, time_limit: u64) -> State<'a> {
+        let mut state = self.clone();
+        state.tally();
+
+        state.actors[0].free_at = time_limit;
+        state.actors.sort_by_key(|a| a.free_at);
+
+        state
+    }
+
+    // The first actor walks to `new_node` and opens it.
+    fn update(&self, conn: &ValveNodeConnection, new_node: &'a ValveNode) -> State<'a> {
+        let mut state = self.clone();
+        state.tally();
+
+        let actor = &mut state.actors[0];
+        actor.free_at += conn.cost + 1;
+        actor.node = new_node;
+        actor.opening = new_node.valve.flow_rate;
+
+        state.visited.insert(new_node.id);
+        state.opened = Some(Rc::new(Opened {
+            minute: actor.free_at,
+            actor: actor.index,
+            valve: new_node.id,
+            before: state.opened.take(),
+        }));
+        state.actors.sort_by_key(|a| a.free_at);
+
+        state
+    }
+}
+
+// How `ValveOptimizer` finds the best plan. Both find the best there is.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Solver {
+    // Tries every order everyone could open the valves in, all at once.
+    Search,
+    // Works out the best anyone could do alone with each set of valves, then shares the valves
+    // out between everyone.
+    #[default]
+    Bitmask,
+}
+
+// Works out the most pressure that can be released, and how. The start valve is never opened,
+// as in the puzzle, where it's stuck.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValveOptimizer {
+    pub actors: usize,
+    pub minutes: u64,
+    pub start: String,
+    pub solver: Solver,
+}
+
+// The best way found to open the valves.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Plan {
+    pub released: u64,
+    // In the order the valves are opened.
+    pub schedule: Vec<Opening>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Opening {
+    // The minute the valve's opened, and starts releasing pressure.
+    pub minute: u64,
+    // Counting from 0, which is you.
+    pub actor: usize,
+    pub valve: String,
+}
+
+impl ValveOptimizer {
+    pub fn new(actors: usize, minutes: u64) -> ValveOptimizer {
+        ValveOptimizer {
+            actors,
+            minutes,
+            start: START_VALVE.to_owned(),
+            solver: Solver::default(),
+        }
+    }
+
+    // The puzzle's own settings for each part.
+    pub fn for_part(part: Part) -> ValveOptimizer {
+        match part {
+            Part::One => ValveOptimizer::new(1, 30),
+            // Teaching the elephant costs 4 of the 30 minutes.
+            Part::Two => ValveOptimizer::new(2, 26),
+        }
+    }
+
+    pub fn with_solver(self, solver: Solver) -> ValveOptimizer {
+        ValveOptimizer { solver, ..self }
+    }
+
+    pub fn with_start(self, start: &str) -> ValveOptimizer {
+        ValveOptimizer {
+            start: start.to_owned(),
+            ..self
+        }
+    }
+
+    pub fn optimize(&self, input: &str) -> Result<Plan, SolveError> {
+        if self.actors == 0 {
+            return Err(Error::NoActors.into());
+        }
+
+        let graph = parse_graph(input, &self.start)?;
+
+        let (released, routes) = match self.solver {
+            Solver::Search => {
+                let best = max_released(&graph, self.actors, self.minutes);
+                (best.released, vec![best.opened])
+            }
+            Solver::Bitmask => share_valves(&graph, self.actors, self.minutes)?,
+        };
+
+        let mut schedule = routes
+            .iter()
+            .flat_map(|route| std::iter::successors(route.as_deref(), |o| o.before.as_deref()))
+            .map(|o| Opening {
+                minute: o.minute,
+                actor: o.actor,
+                valve: graph.get(o.valve).valve.name.clone(),
+            })
+            .collect::<Vec<_>>();
+        schedule.sort_by_key(|o| (o.minute, o.actor));
+
+        Ok(Plan { released, schedule })
+    }
+}
+
+// Which graph `ValveDiagram` draws.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Network {
+    // Every valve and tunnel, as in the input.
+    #[default]
+    Tunnels,
+    // Only the start and the valves worth opening, with how many minutes it takes to walk
+    // between them. This is what the solvers see.
+    Valves,
+}
+
+impl std::str::FromStr for Network {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Network, String> {
+        match s {
+            "tunnels" => Ok(Network::Tunnels),
+            "valves" => Ok(Network::Valves),
+            _ => Err(format!("invalid network: {:?}", s)),
+        }
+    }
+}
+
+// The colours each actor's route is drawn in, going round again if there are more actors.
+const ROUTE_COLOURS: &[&str] = &[
+    "firebrick",
+    "royalblue",
+    "forestgreen",
+    "darkorange",
+    "purple",
+];
+
+// Draws the valves as a Graphviz DOT graph, for seeing what's going on with an awkward input:
+//
+//     dot -Tsvg valves.dot > valves.svg
+//
+// Tunnels that go both ways are drawn once, without arrows. With a plan, everyone's route is
+// drawn over the top, and the valves they open are outlined, each actor in their own colour.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValveDiagram {
+    pub network: Network,
+    pub start: String,
+    pub plan: Option<Plan>,
+}
+
+impl ValveDiagram {
+    pub fn new(network: Network) -> ValveDiagram {
+        ValveDiagram {
+            network,
+            start: START_VALVE.to_owned(),
+            plan: None,
+        }
+    }
+
+    pub fn with_start(self, start: &str) -> ValveDiagram {
+        ValveDiagram {
+            start: start.to_owned(),
+            ..self
+        }
+    }
+
+    pub fn with_plan(self, plan: Plan) -> ValveDiagram {
+        ValveDiagram {
+            plan: Some(plan),
+            ..self
+        }
+    }
+
+    pub fn render(&self, input: &str) -> Result<String, SolveError> {
+        let valves = nom_parse_to_owned(parser::parse_input, input)?;
+        let graph = ValveGraph::build(&valves, &self.start)?;
+
+        let tunnels = valves
+            .iter()
+            .map(|(valve, tunnels)| (valve.name.as_str(), tunnels))
+            .collect::<HashMap<_, _>>();
+        let opened = self
+            .plan
+            .iter()
+            .flat_map(|plan| &plan.schedule)
+            .map(|o| (o.valve.as_str(), o))
+            .collect::<HashMap<_, _>>();
+
+        // The valves, and the ways out of each with how long they take, in the order they
+        // should be drawn.
+        let (name, nodes, edges) = match self.network {
+            Network::Tunnels => {
+                let nodes = valves.iter().map(|(valve, _)| valve).collect::<Vec<_>>();
+                let edges = valves
+                    .iter()
+                    .map(|(valve, tunnels)| {
+                        let edges = tunnels.iter().map(|to| (to.as_str(), 1)).collect();
+                        (valve.name.as_str(), edges)
+                    })
+                    .collect::<HashMap<_, Vec<_>>>();
+
+                ("tunnels", nodes, edges)
+            }
+            Network::Valves => {
+                let nodes = graph.nodes().map(|node| &node.valve).collect::<Vec<_>>();
+                let edges = graph
+                    .nodes()
+                    .map(|node| {
+                        let mut edges = node
+                            .connections
+                            .iter()
+                            .map(|conn| (graph.get(conn.target).valve.name.as_str(), conn.cost))
+                            .collect::<Vec<_>>();
+                        edges.sort();
+                        (node.valve.name.as_str(), edges)
+                    })
+                    .collect::<HashMap<_, Vec<_>>>();
+
+                ("valves", nodes, edges)
+            }
+        };
+        // Only the valves being drawn, so not ones without any flow in the valves network.
+        if let Some(&name) = opened.keys().find(|&&name| !edges.contains_key(name)) {
+            return Err(Error::NotInInput(name.to_owned()).into());
+        }
+        let cost = |from: &str, to: &str| {
+            edges
+                .get(from)?
+                .iter()
+                .find(|&&(name, _)| name == to)
+                .map(|&(_, cost)| cost)
+        };
+
+        let mut out = String::new();
+        writeln!(out, "digraph {} {{", name).expect("writing to a string");
+
+        for valve in &nodes {
+            let mut attrs = vec![];
+            match opened.get(valve.name.as_str()) {
+                Some(o) => {
+                    attrs.push(format!(
+                        "label=\"{}\\n{}\\nminute {}\"",
+                        valve.name, valve.flow_rate, o.minute
+                    ));
+                    attrs.push(format!("color={}, penwidth=2", colour(o.actor)));
+                }
+                None => attrs.push(format!("label=\"{}\\n{}\"", valve.name, valve.flow_rate)),
+            }
+            if valve.name == self.start {
+                attrs.push("shape=doublecircle".to_owned());
+            } else if valve.flow_rate == 0 {
+                attrs.push("style=dashed".to_owned());
+            }
+
+            writeln!(out, "    \"{}\" [{}];", valve.name, attrs.join(", "))
+                .expect("writing to a string");
+        }
+
+        for valve in &nodes {
+            let from = valve.name.as_str();
+            for &(to, c) in &edges[from] {
+                let both_ways = cost(to, from) == Some(c);
+                if both_ways && to < from {
+                    continue;
+                }
+
+                let mut attrs = vec![];
+                if self.network == Network::Valves {
+                    attrs.push(format!("label={}", c));
+                }
+                if both_ways {
+                    attrs.push("dir=none".to_owned());
+                }
+                write_edge(&mut out, from, to, &attrs);
+            }
+        }
+
+        for (actor, route) in self.routes().into_iter().enumerate() {
+            let attrs = [format!("color={}, penwidth=2", colour(actor))];
+
+            for (from, to) in route.iter().zip(route.iter().skip(1)) {
+                let steps = match self.network {
+                    Network::Tunnels => {
+                        let graph = |name: &&str| {
+                            tunnels
+                                .get(name)
+                                .copied()
+                                .into_iter()
+                                .flatten()
+                                .map(|next| (next.as_str(), 1))
+                        };
+                        search::bfs(&graph, [*from], |name| name == to)
+                            .ok_or_else(|| Error::NoWay(from.to_string(), to.to_string()))?
+                            .nodes
+                    }
+                    Network::Valves => {
+                        cost(from, to)
+                            .ok_or_else(|| Error::NoWay(from.to_string(), to.to_string()))?;
+                        vec![*from, *to]
+                    }
+                };
+
+                for (a, b) in steps.iter().zip(steps.iter().skip(1)) {
+                    write_edge(&mut out, a, b, &attrs);
+                }
+            }
+        }
+
+        out.push_str("}\n");
+
+        Ok(out)
+    }
+
+    // Where each actor goes, starting from the start.
+    fn routes(&self) -> Vec<Vec<&str>> {
+        let Some(plan) = &self.plan else {
+            return vec![];
+        };
+
+        let mut schedule = plan.schedule.iter().collect::<Vec<_>>();
+        schedule.sort_by_key(|o| o.minute);
+
+        let actors = schedule.iter().map(|o| o.actor + 1).max().unwrap_or(0);
+        let mut routes = vec![vec![self.start.as_str()]; actors];
+        for o in schedule {
+            routes[o.actor].push(&o.valve);
+        }
+
+        routes
+    }
+}
+
+fn colour(actor: usize) -> &'static str {
+    ROUTE_COLOURS[actor % ROUTE_COLOURS.len()]
+}
+
+fn write_edge(out: &mut String, from: &str, to: &str, attrs: &[String]) {
+    write!(out, "    \"{}\" -> \"{}\"", from, to).expect("writing to a string");
+    if !attrs.is_empty() {
+        write!(out, " [{}]", attrs.join(", ")).expect("writing to a string");
+    }
+    out.push_str(";\n");
+}
+
+pub struct Day16;
+
+impl Solution for Day16 {
+    fn day(&self) -> u8 {
+        16
+    }
+
+    fn title(&self) -> &'static str {
+        "Proboscidea Volcanium"
+    }
+
+    fn parse(&self, input: &str) -> Result<(), SolveError> {
+        parse_graph(input, START_VALVE)?;
+        Ok(())
+    }
+
+    fn part1(&self, input: &str) -> Result<Answer, SolveError> {
+        part1(input)
+    }
+
+    fn part2(&self, input: &str) -> Result<Answer, SolveError> {
+        part2(input)
+    }
+}
+
+pub fn part1(input: &str) -> Result<Answer, SolveError> {
+    Ok(ValveOptimizer::for_part(Part::One)
+        .optimize(input)?
+        .released
+        .into())
+}
+
+pub fn part2(input: &str) -> Result<Answer, SolveError> {
+    Ok(ValveOptimizer::for_part(Part::Two)
+        .optimize(input)?
+        .released
+        .into())
+}
+
+fn parse_graph(input: &str, start: &str) -> Result<ValveGraph, SolveError> {
+    let valves = nom_parse_to_owned(parser::parse_input, input)?;
+    Ok(ValveGraph::build(&valves, start)?)
+}
+
+fn max_released<'a>(graph: &'a ValveGraph, actors: usize, time_limit: u64) -> State<'a> {
+    let mut max = State::new(graph.start(), actors);
+    let mut current = vec![State::new(graph.start(), actors)];
+
+    while let Some(state) = current.pop() {
+        if state.can_continue(time_limit) {
+            // Stopping early is always an option, as someone else might get to a valve sooner.
+            let retired = state.retire(time_limit);
+
+            let Actor {
+                free_at: time_spent,
+                node,
+                ..
+            } = state.actors[0];
+            let visited = state.visited.clone();
+
+            let iter = node
+                .connections
+                .iter()
+                .filter_map(move |conn| {
+                    let node = graph.get(conn.target);
+                    if visited.contains(&node.id) || time_spent + conn.cost + 1 > time_limit {
+                        None
+                    } else {
+                        Some(state.update(conn, node))
+                    }
+                })
+                .chain(std::iter::once(retired));
+            current.extend(iter);
+        } else {
+            let state = state.finish(time_limit);
+
+            if state.is_valid(time_limit) && state.released > max.released {
+                max = state;
+            }
+        }
+    }
+
+    max
+}
+
+// Everyone's route, each as a list of the valves they opened, with the total they release.
+type Routes = (u64, Vec<Option<Rc<Opened>>>);
+
+// Nobody can open a valve someone else has, so the valves are split up into a set for each
+// actor. With the best anyone could do alone with each set worked out, that's only a question of
+// finding the best way to split them.
+fn share_valves(graph: &ValveGraph, actors: usize, time_limit: u64) -> Result<Routes, Error> {
+    let valves = graph
+        .nodes()
+        .filter(|node| node.id != graph.start().id)
+        .map(|node| node.id)
+        .collect::<Vec<_>>();
+    if valves.len() > MAX_BITMASK_VALVES {
+        return Err(Error::TooManyValves(valves.len()));
+    }
+
+    let bits = valves
+        .iter()
+        .enumerate()
+        .map(|(bit, &id)| (id, 1 << bit))
+        .collect::<HashMap<_, usize>>();
+    let all = (1 << valves.len()) - 1;
+
+    let mut alone = vec![(0, None); all + 1];
+    let route = Route {
+        node: graph.start(),
+        minute: 0,
+        mask: 0,
+        released: 0,
+        opened: None,
+    };
+    walk(graph, &bits, time_limit, route, &mut alone);
+
+    // The best from any of the valves in each set, and the ones that make it. Fewer valves can
+    // be better when there isn't time to get round them all.
+    let mut up_to = alone
+        .iter()
+        .enumerate()
+        .map(|(mask, &(released, _))| (released, mask))
+        .collect::<Vec<_>>();
+    for bit in bits.values() {
+        for mask in 0..=all {
+            if mask & bit != 0 && up_to[mask ^ bit].0 > up_to[mask].0 {
+                up_to[mask] = up_to[mask ^ bit];
+            }
+        }
+    }
+
+    // For each number of actors, the best they can do with each set between them, and the
+    // share of it the last of them gets.
+    let mut teams = vec![(0..=all)
+        .map(|mask| (up_to[mask].0, mask))
+        .collect::<Vec<_>>()];
+    for _ in 1..actors {
+        let team = teams.last().expect("one actor at least");
+        let bigger = (0..=all)
+            .map(|mask| {
+                submasks(mask)
+                    .map(|share| (up_to[share].0 + team[mask ^ share].0, share))
+                    .max_by_key(|&(released, _)| released)
+                    .expect("every set is a subset of itself")
+            })
+            .collect();
+        teams.push(bigger);
+    }
+
+    let released = teams.last().expect("one actor at least")[all].0;
+
+    // Hand the shares back out, from the last actor to join to the first.
+    let mut mask = all;
+    let mut routes = vec![None; actors];
+    for (actor, team) in teams.iter().enumerate().rev() {
+        let share = team[mask].1;
+        routes[actor] = relabel(&alone[up_to[share].1].1, actor);
+        mask ^= share;
+    }
+
+    Ok((released, routes))
+}
+
+// One actor on their own, partway round.
+struct Route<'a> {
+    node: &'a ValveNode,
+    minute: u64,
+    // The valves opened so far.
+    mask: usize,
+    // Counting everything the open valves will release by the time limit.
+    released: u64,
+    opened: Option<Rc<Opened>>,
+}
+
+// Goes everywhere one actor could go alone, keeping the best route for each set of valves.
+fn walk(
+    graph: &ValveGraph,
+    bits: &HashMap<Id<ValveNode>, usize>,
+    time_limit: u64,
+    route: Route,
+    best: &mut [(u64, Option<Rc<Opened>>)],
+) {
+    if route.released > best[route.mask].0 {
+        best[route.mask] = (route.released, route.opened.clone());
+    }
+
+    for conn in &route.node.connections {
+        let Some(&bit) = bits.get(&conn.target) else {
+            continue;
+        };
+        let minute = route.minute + conn.cost + 1;
+        if route.mask & bit != 0 || minute >= time_limit {
+            continue;
+        }
+
+        let node = graph.get(conn.target);
+        let next = Route {
+            node,
+            minute,
+            mask: route.mask | bit,
+            released: route.released + (time_limit - minute) * node.valve.flow_rate,
+            opened: Some(Rc::new(Opened {
+                minute,
+                actor: 0,
+                valve: node.id,
+                before: route.opened.clone(),
+            })),
+        };
+
+        walk(graph, bits, time_limit, next, best);
+    }
+}
+
+// Every subset of `mask`, including itself and the empty set.
+fn submasks(mask: usize) -> impl Iterator<Item = usize> {
+    std::iter::successors(Some(mask), move |&share| {
+        (share != 0).then(|| (share - 1) & mask)
+    })
+}
+
+// The same route, opened by someone else.
+fn relabel(route: &Option<Rc<Opened>>, actor: usize) -> Option<Rc<Opened>> {
+    let opened = std::iter::successors(route.as_deref(), |o| o.before.as_deref());
+    let valves = opened.collect::<Vec<_>>();
+
+    valves.into_iter().rev().fold(None, |before, o| {
+        Some(Rc::new(Opened {
+            actor,
+            before,
+            ..*o
+        }))
+    })
+}
+
+mod graph {
+    use super::*;
+
+    use std::collections::HashMap;
+
+    use id_arena::{Arena, Id};
+
+    use crate::search;
+
+    #[derive(Debug)]
+    pub(super) struct ValveNode {
+        pub id: Id<ValveNode>,
+        pub valve: Valve,
+        pub connections: Vec<ValveNodeConnection>,
+    }
+
+    #[derive(Debug)]
+    pub(super) struct ValveNodeConnection {
+        pub target: Id<ValveNode>,
+        pub cost: u64,
+    }
+
+    pub(super) struct ValveGraph {
+        arena: Arena<ValveNode>,
+        start: Id<ValveNode>,
+    }
+
+    impl ValveGraph {
+        pub(super) fn build(v: &[(Valve, Vec<String>)], start: &str) -> Result<ValveGraph, Error> {
+            v.iter()
+                .fold(ValveGraphBuilder::new(start), ValveGraphBuilder::accumulate)
+                .finish()
+        }
+
+        pub fn start(&self) -> &ValveNode {
+            self.get(self.start)
+        }
+
+        pub fn get(&self, id: Id<ValveNode>) -> &ValveNode {
+            self.arena.get(id).expect("valid id")
+        }
+
+        // The start, and every valve worth opening.
+        pub fn nodes(&self) -> impl Iterator<Item = &ValveNode> {
+            self.arena.iter().map(|(_, node)| node)
+        }
+    }
+
+    struct ValveGraphBuilder<'a> {
+        start: &'a str,
+        arena: Arena<ValveNode>,
+        connections: HashMap<&'a str, Vec<&'a str>>,
+        ids: HashMap<&'a str, Id<ValveNode>>,
+    }
+
+    impl<'a> ValveGraphBuilder<'a> {
+        fn new(start: &'a str) -> ValveGraphBuilder<'a> {
+            ValveGraphBuilder {
+                start,
+                arena: Arena::new(),
+                connections: HashMap::new(),
+                ids: HashMap::new(),
+            }
+        }
+
+        fn accumulate(
+            mut self,
+            (valve, connections): &'a (Valve, Vec<String>),
+        ) -> ValveGraphBuilder<'a> {
+            self.connections.insert(
+                &valve.name,
+                connections.iter().map(String::as_str).collect(),
+            );
+
+            if valve.name == self.start || valve.flow_rate > 0 {
+                let id = self.arena.alloc_with_id(|id| ValveNode {
+                    id,
+                    valve: valve.clone(),
+                    connections: Default::default(),
+                });
+
+                self.ids.insert(&valve.name, id);
+            }
+
+            self
+        }
+
+        fn finish(self) -> Result<ValveGraph, Error> {
+            let ValveGraphBuilder {
+                start,
+                mut arena,
+                connections,
+                ids,
+            } = self;
+
+            for tunnels in connections.values() {
+                if let Some(&unknown) = tunnels.iter().find(|n| !connections.contains_key(*n)) {
+                    return Err(Error::UnknownValve(unknown.to_owned()));
+                }
+            }
+
+            for (&name, &id) in &ids {
+                let node = arena.get_mut(id).expect("valid id");
+                node.connections = walk_connections(name, &connections, &ids);
+            }
+
+            let start = *ids
+                .get(start)
+                .ok_or_else(|| Error::NoStartValve(start.to_owned()))?;
+
+            Ok(ValveGraph { arena, start })
+        }
+    }
+
+    // The valves worth opening that can be reached from `start`, and how many minutes it takes
+    // to walk to each.
+    fn walk_connections<'a>(
+        start: &'a str,
+        connections: &HashMap<&'a str, Vec<&'a str>>,
+        ids: &HashMap<&'a str, Id<ValveNode>>,
+    ) -> Vec<ValveNodeConnection> {
+        let tunnels = |name: &&'a str| connections[name].iter().map(|&next| (next, 1));
+
+        search::distances(&tunnels, start)
+            .into_iter()
+            .filter(|&(_, cost)| cost > 0)
+            .filter_map(|(name, cost)| {
+                let &target = ids.get(name)?;
+                Some(ValveNodeConnection { target, cost })
+            })
+            .collect()
+    }
+}
+
+mod parser {
+    use super::*;
+
+    use crate::parser::base10_numeric;
+
+    use nom::{
+        branch::alt,
+        bytes::complete::tag,
+        character::complete::{alpha1, line_ending},
+        combinator::eof,
+        multi::{many0, separated_list1},
+        sequence::{preceded, terminated, tuple},
+        IResult, Parser,
+    };
+
+    fn valve_fields(input: &str) -> IResult<&str, (Valve, Vec<String>)> {
+        tuple((
+            preceded(tag("Valve "), alpha1),
+            preceded(tag(" has flow rate="), base10_numeric),
+            preceded(
+                alt((
+                    tag("; tunnels lead to valves "),
+                    tag("; tunnel leads to valve "),
+                )),
+                separated_list1(tag(", "), alpha1),
+            ),
+        ))
+        .map(|(name, flow_rate, tunnels)| {
+            (
+                Valve {
+                    name: name.to_owned(),
+                    flow_rate,
+                },
+                tunnels.into_iter().map(str::to_owned).collect(),
+            )
+        })
+        .parse(input)
+    }
+
+    fn valves(input: &str) -> IResult<&str, Vec<(Valve, Vec<String>)>> {
+        separated_list1(line_ending, valve_fields).parse(input)
+    }
+
+    pub(super) fn parse_input(input: &str) -> IResult<&str, Vec<(Valve, Vec<String>)>> {
+        terminated(valves, tuple((many0(line_ending), eof))).parse(input)
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[test]
+        fn test_parse_valves() {
+            let input = "Valve AA has flow rate=0; tunnels lead to valves DD, II\n\
+                         Valve DD has flow rate=20; tunnels lead to valves AA\n\
+                         Valve II has flow rate=0; tunnels lead to valves AA, JJ\n\
+                         Valve JJ has flow rate=21; tunnel leads to valve II";
+
+            let result = valves(input);
+            assert!(result.is_ok());
+            let (remaining_input, entries) = result.unwrap();
+
+            let mut iter = entries.into_iter();
+
+            let (node, connections) = iter.next().expect("AA");
+            assert_eq!(node.name, "AA");
+            assert_eq!(node.flow_rate, 0);
+            assert_eq!(connections, vec!["DD", "II"]);
+
+            let (node, connections) = iter.next().expect("DD");
+            assert_eq!(node.name, "DD");
+            assert_eq!(node.flow_rate, 20);
+            assert_eq!(connections, vec!["AA"]);
+
+            let (node, connections) = iter.next().expect("II");
+            assert_eq!(node.name, "II");
+            assert_eq!(node.flow_rate, 0);
+            assert_eq!(connections, vec!["AA", "JJ"]);
+
+            let (node, connections) = iter.next().expect("JJ");
+            assert_eq!(node.name, "JJ");
+            assert_eq!(node.flow_rate, 21);
+            assert_eq!(connections, vec!["II"]);
+
+            assert_eq!(remaining_input, "");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use itertools::Itertools;
+
+    const TIME_LIMIT: u64 = 26;
+
+    #[test]
+    fn test_state_update_1() {
+        let input = "Valve AA has flow rate=0; tunnels lead to valves DD, II\n\
+                     Valve DD has flow rate=20; tunnels lead to valves AA\n\
+                     Valve II has flow rate=0; tunnels lead to valves AA, JJ\n\
+                     Valve JJ has flow rate=21; tunnel leads to valve II, KK\n\
+                     Valve KK has flow rate=5; tunnel leads to valve JJ";
+
+        let graph = parse_graph(input, START_VALVE).unwrap();
+
+        let state = State::new(graph.start(), 1);
+
+        fn f<'a>(
+            graph: &'a ValveGraph,
+            node: &'a ValveNode,
+            name: &str,
+        ) -> (&'a ValveNodeConnection, &'a ValveNode) {
+            node.connections
+                .iter()
+                .find_map(|c| {
+                    let node = graph.get(c.target);
+                    if node.valve.name == name {
+                        Some((c, node))
+                    } else {
+                        None
+                    }
+                })
+                .unwrap()
+        }
+
+        let (dd_conn, dd_node) = f(&graph, graph.start(), "DD");
+
+        let state = state.update(dd_conn, dd_node);
+        assert_eq!(
+            state
+                .actors
+                .iter()
+                .map(|a| (a.free_at, a.node.id))
+                .collect::<Vec<_>>(),
+            vec![(2u64, dd_node.id)]
+        );
+
+        assert_eq!(state.flow_rate, 0);
+        assert_eq!(state.released, 0);
+        assert_eq!(state.time_tallied, 0);
+        assert_eq!(
+            state.visited,
+            [graph.start().id, dd_node.id].into_iter().collect()
+        );
+
+        let (jj_conn, jj_node) = f(&graph, dd_node, "JJ");
+
+        let state = state.update(jj_conn, jj_node);
+        assert_eq!(
+            state
+                .actors
+                .iter()
+                .map(|a| (a.free_at, a.node.id))
+                .collect::<Vec<_>>(),
+            vec![(6u64, jj_node.id)]
+        );
+
+        assert_eq!(state.flow_rate, 20);
+        assert_eq!(state.released, 0);
+        assert_eq!(state.time_tallied, 2);
+        assert_eq!(
+            state.visited,
+            [graph.start().id, dd_node.id, jj_node.id]
+                .into_iter()
+                .collect()
+        );
+
+        let (kk_conn, kk_node) = f(&graph, jj_node, "KK");
+
+        let state = state.update(kk_conn, kk_node);
+        assert_eq!(
+            state
+                .actors
+                .iter()
+                .map(|a| (a.free_at, a.node.id))
+                .collect::<Vec<_>>(),
+            vec![(8u64, kk_node.id)]
+        );
+
+        assert_eq!(state.flow_rate, 41);
+        assert_eq!(state.released, 80);
+        assert_eq!(state.time_tallied, 6);
+        assert_eq!(
+            state.visited,
+            [graph.start().id, dd_node.id, jj_node.id, kk_node.id]
+                .into_iter()
+                .collect()
+        );
+
+        let state = state.finish(TIME_LIMIT);
+
+        assert_eq!(
+            state
+                .actors
+                .iter()
+                .map(|a| (a.free_at, a.node.id))
+                .collect::<Vec<_>>(),
+            vec![(TIME_LIMIT, kk_node.id)]
+        );
+
+        assert_eq!(state.flow_rate, 46);
+        assert_eq!(state.released, 162 + (TIME_LIMIT - 8) * 46);
+        assert_eq!(state.time_tallied, TIME_LIMIT);
+        assert_eq!(
+            state.visited,
+            [graph.start().id, dd_node.id, jj_node.id, kk_node.id]
+                .into_iter()
+                .collect()
+        );
+
+        let state_idle_again = state.finish(TIME_LIMIT);
+
+        assert_eq!(
+            state
+                .actors
+                .iter()
+                .map(|a| (a.free_at, a.node.id))
+                .collect::<Vec<_>>(),
+            state_idle_again
+                .actors
+                .iter()
+                .map(|a| (a.free_at, a.node.id))
+                .collect::<Vec<_>>(),
+        );
+
+        assert_eq!(state.flow_rate, state_idle_again.flow_rate);
+        assert_eq!(state.released, state_idle_again.released);
+        assert_eq!(state.time_tallied, state_idle_again.time_tallied);
+        assert_eq!(state.visited, state_idle_again.visited);
+    }
+
+    const EXAMPLE: &str = "\
+        Valve AA has flow rate=0; tunnels lead to valves DD, II, BB\n\
+        Valve BB has flow rate=13; tunnels lead to valves CC, AA\n\
+        Valve CC has flow rate=2; tunnels lead to valves DD, BB\n\
+        Valve DD has flow rate=20; tunnels lead to valves CC, AA, EE\n\
+        Valve EE has flow rate=3; tunnels lead to valves FF, DD\n\
+        Valve FF has flow rate=0; tunnels lead to valves EE, GG\n\
+        Valve GG has flow rate=0; tunnels lead to valves FF, HH\n\
+        Valve HH has flow rate=22; tunnel leads to valve GG\n\
+        Valve II has flow rate=0; tunnels lead to valves AA, JJ\n\
+        Valve JJ has flow rate=21; tunnel leads to valve II";
+
+    #[test]
+    fn test_example_part1() {
+        assert_eq!(part1(EXAMPLE).unwrap(), Answer::Number(1651));
+    }
+
+    #[test]
+    fn test_example_part2() {
+        assert_eq!(part2(EXAMPLE).unwrap(), Answer::Number(1707));
+    }
+
+    #[test]
+    fn test_schedule() {
+        let opening = |minute, actor, valve: &str| Opening {
+            minute,
+            actor,
+            valve: valve.to_owned(),
+        };
+
+        // As in the puzzle's walkthrough.
+        let plan = ValveOptimizer::new(1, 30).optimize(EXAMPLE).unwrap();
+        assert_eq!(plan.released, 1651);
+        assert_eq!(
+            plan.schedule,
+            vec![
+                opening(2, 0, "DD"),
+                opening(5, 0, "BB"),
+                opening(9, 0, "JJ"),
+                opening(17, 0, "HH"),
+                opening(21, 0, "EE"),
+                opening(24, 0, "CC"),
+            ]
+        );
+
+        // The total always comes to what the schedule says it should, and the solvers agree on
+        // what the best total is, if not always on how to get it.
+        let flow_rates = parser::parse_input(EXAMPLE).unwrap().1;
+        for (actors, minutes, start) in [(1, 30, "AA"), (2, 26, "AA"), (3, 15, "AA"), (2, 10, "JJ")]
+        {
+            let plans = [Solver::Search, Solver::Bitmask].map(|solver| {
+                ValveOptimizer::new(actors, minutes)
+                    .with_start(start)
+                    .with_solver(solver)
+                    .optimize(EXAMPLE)
+                    .unwrap()
+            });
+            assert_eq!(
+                plans[0].released, plans[1].released,
+                "{} actors for {} minutes from {}",
+                actors, minutes, start
+            );
+
+            for plan in plans {
+                let released = plan
+                    .schedule
+                    .iter()
+                    .map(|o| {
+                        let (valve, _) =
+                            flow_rates.iter().find(|(v, _)| v.name == o.valve).unwrap();
+                        (minutes - o.minute) * valve.flow_rate
+                    })
+                    .sum::<u64>();
+
+                assert_eq!(released, plan.released);
+                assert!(plan
+                    .schedule
+                    .iter()
+                    .all(|o| o.actor < actors && o.valve != start));
+                assert!(plan.schedule.iter().map(|o| &o.valve).all_unique());
+            }
+        }
+
+        // More hands make light work.
+        let released = |actors| {
+            ValveOptimizer::new(actors, 10)
+                .optimize(EXAMPLE)
+                .unwrap()
+                .released
+        };
+        assert!(released(1) < released(2) && released(2) < released(3));
+    }
+
+    #[test]
+    fn test_optimizer_errors() {
+        let chain = (0..=MAX_BITMASK_VALVES)
+            .map(|i| {
+                let name = |i| format!("B{}", char::from(b'A' + i as u8));
+                format!(
+                    "Valve {} has flow rate=1; tunnels lead to valves {}",
+                    name(i),
+                    name((i + 1) % (MAX_BITMASK_VALVES + 1))
+                )
+            })
+            .chain(["Valve AA has flow rate=0; tunnels lead to valves BA".to_owned()])
+            .join("\n");
+        let e = ValveOptimizer::new(1, 30).optimize(&chain).unwrap_err();
+        assert_eq!(
+            e.to_string(),
+            "21 valves is too many to share out, the most is 20"
+        );
+
+        let e = ValveOptimizer::new(0, 30).optimize(EXAMPLE).unwrap_err();
+        assert_eq!(e.to_string(), "nobody to open the valves");
+
+        let e = ValveOptimizer::new(1, 30)
+            .with_start("ZZ")
+            .optimize(EXAMPLE)
+            .unwrap_err();
+        assert_eq!(e.to_string(), "no valve named ZZ");
+    }
+
+    #[test]
+    fn test_diagram() {
+        // The tunnel back from CC to AA only goes one way.
+        let input = "\
+            Valve AA has flow rate=0; tunnels lead to valves BB\n\
+            Valve BB has flow rate=0; tunnels lead to valves AA, CC\n\
+            Valve CC has flow rate=5; tunnels lead to valves BB, AA";
+        let plan = ValveOptimizer::new(1, 5).optimize(input).unwrap();
+
+        let diagram = ValveDiagram::new(Network::Tunnels).with_plan(plan.clone());
+        assert_eq!(
+            diagram.render(input).unwrap(),
+            "digraph tunnels {\n    \
+                 \"AA\" [label=\"AA\\n0\", shape=doublecircle];\n    \
+                 \"BB\" [label=\"BB\\n0\", style=dashed];\n    \
+                 \"CC\" [label=\"CC\\n5\\nminute 3\", color=firebrick, penwidth=2];\n    \
+                 \"AA\" -> \"BB\" [dir=none];\n    \
+                 \"BB\" -> \"CC\" [dir=none];\n    \
+                 \"CC\" -> \"AA\";\n    \
+                 \"AA\" -> \"BB\" [color=firebrick, penwidth=2];\n    \
+                 \"BB\" -> \"CC\" [color=firebrick, penwidth=2];\n\
+             }\n"
+        );
+
+        let diagram = ValveDiagram::new(Network::Valves).with_plan(plan);
+        assert_eq!(
+            diagram.render(input).unwrap(),
+            "digraph valves {\n    \
+                 \"AA\" [label=\"AA\\n0\", shape=doublecircle];\n    \
+                 \"CC\" [label=\"CC\\n5\\nminute 3\", color=firebrick, penwidth=2];\n    \
+                 \"AA\" -> \"CC\" [label=2];\n    \
+                 \"CC\" -> \"AA\" [label=1];\n    \
+                 \"AA\" -> \"CC\" [color=firebrick, penwidth=2];\n\
+             }\n"
+        );
+
+        // Everyone's route takes a step for every minute they spend walking.
+        let plan = ValveOptimizer::new(2, TIME_LIMIT)
+            .optimize(EXAMPLE)
+            .unwrap();
+        let dot = ValveDiagram::new(Network::Tunnels)
+            .with_plan(plan.clone())
+            .render(EXAMPLE)
+            .unwrap();
+        for actor in 0..2 {
+            let walked = plan
+                .schedule
+                .iter()
+                .filter(|o| o.actor == actor)
+                .fold((0, 0), |(walked, last), o| {
+                    (walked + o.minute - last - 1, o.minute)
+                })
+                .0;
+            let steps = dot
+                .lines()
+                .filter(|line| line.contains("->") && line.contains(colour(actor)))
+                .count();
+            assert_eq!(steps as u64, walked, "actor {}", actor);
+        }
+
+        let plan = Plan {
+            released: 0,
+            schedule: vec![Opening {
+                minute: 1,
+                actor: 0,
+                valve: "ZZ".to_owned(),
+            }],
+        };
+        let e = ValveDiagram::new(Network::Tunnels)
+            .with_plan(plan)
+            .render(EXAMPLE)
+            .unwrap_err();
+        assert_eq!(e.to_string(), "plan opens valve ZZ, which isn't there");
+
+        // FF has no flow, so it isn't in the valves network.
+        let plan = Plan {
+            released: 0,
+            schedule: vec![Opening {
+                minute: 1,
+                actor: 0,
+                valve: "FF".to_owned(),
+            }],
+        };
+        let e = ValveDiagram::new(Network::Valves)
+            .with_plan(plan)
+            .render(EXAMPLE)
+            .unwrap_err();
+        assert_eq!(e.to_string(), "plan opens valve FF, which isn't there");
+
+        // There's no way back to AA once you've left it.
+        let input = "\
+            Valve AA has flow rate=0; tunnels lead to valves BB\n\
+            Valve BB has flow rate=1; tunnels lead to valves CC\n\
+            Valve CC has flow rate=1; tunnels lead to valves BB";
+        let plan = Plan {
+            released: 0,
+            schedule: vec![Opening {
+                minute: 3,
+                actor: 0,
+                valve: "AA".to_owned(),
+            }],
+        };
+        let e = ValveDiagram::new(Network::Tunnels)
+            .with_start("CC")
+            .with_plan(plan)
+            .render(input)
+            .unwrap_err();
+        assert_eq!(e.to_string(), "no way from CC to AA");
+    }
+}
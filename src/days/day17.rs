@@ -0,0 +1,378 @@
+use crate::cycle::{self, Cycle};
+use crate::geom::{Point2, Vec2};
+use crate::solution::{Answer, DayError, Solution, SolveError};
+
+#[derive(Debug)]
+enum Error {
+    InvalidJet(u8),
+    NoJets,
+    NoCycle,
+}
+
+impl std::error::Error for Error {}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::InvalidJet(b) => write!(f, "invalid jet direction: {:?}", char::from(*b)),
+            Error::NoJets => f.write_str("no jets of hot gas"),
+            Error::NoCycle => f.write_str("no cycle detected"),
+        }
+    }
+}
+
+impl DayError for Error {
+    fn day(&self) -> u8 {
+        17
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Direction {
+    Left,
+    Right,
+}
+
+impl TryFrom<u8> for Direction {
+    type Error = Error;
+
+    fn try_from(c: u8) -> Result<Direction, Error> {
+        match c {
+            b'<' => Ok(Direction::Left),
+            b'>' => Ok(Direction::Right),
+            _ => Err(Error::InvalidJet(c)),
+        }
+    }
+}
+
+impl Direction {
+    fn apply(self, pos: Point2) -> Point2 {
+        match self {
+            Direction::Left => pos - Vec2::new(1, 0),
+            Direction::Right => pos + Vec2::new(1, 0),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Block {
+    Rock,
+    Empty,
+}
+
+#[derive(Debug)]
+struct Cave<const WIDTH: usize> {
+    blocks: Vec<[Block; WIDTH]>,
+    top: Option<usize>,
+}
+
+impl<const WIDTH: usize> Cave<WIDTH> {
+    const INCREMENT: [[Block; WIDTH]; 32] = [[Block::Empty; WIDTH]; 32];
+
+    fn new() -> Self {
+        let blocks = Vec::from(Cave::<WIDTH>::INCREMENT);
+
+        Self { blocks, top: None }
+    }
+
+    fn height(&self) -> usize {
+        self.top.map_or(0, |top| top + 1)
+    }
+
+    // The cave's rows count upwards from the floor.
+    fn rock_start_position(&self) -> Point2 {
+        Point2::new(2, self.top.map_or(3, |y| y as i64 + 4))
+    }
+
+    fn get(&self, pos: Point2) -> Option<Block> {
+        let (x, y) = (usize::try_from(pos.x).ok()?, usize::try_from(pos.y).ok()?);
+        self.blocks.get(y).and_then(|v| v.get(x)).copied()
+    }
+
+    // Only where `get` has found room for it.
+    fn put_rock(&mut self, pos: Point2) {
+        let (x, y) = (pos.x as usize, pos.y as usize);
+        self.blocks[y][x] = Block::Rock;
+
+        match self.top {
+            Some(top) if top > y => {}
+            _ => {
+                self.top = Some(y);
+
+                if y + 10 > self.blocks.len() {
+                    self.blocks.extend_from_slice(&Cave::<WIDTH>::INCREMENT);
+                }
+            }
+        }
+    }
+
+    fn valid(&self, rock: &dyn RockShape, offset: Point2) -> bool {
+        rock.positions()
+            .all(|pos| self.get(offset + pos) == Some(Block::Empty))
+    }
+
+    fn cache_key(&self) -> [u8; 100] {
+        let mut out = [0; 100];
+        for (row, b) in self
+            .blocks
+            .iter()
+            .skip(self.top.unwrap_or(0).saturating_sub(out.len()))
+            .zip(out.iter_mut())
+        {
+            for (j, block) in row.iter().enumerate() {
+                if *block == Block::Rock {
+                    *b |= 1 << j;
+                }
+            }
+        }
+
+        out
+    }
+}
+
+trait RockShape {
+    fn positions(&self) -> RockPosIter;
+}
+
+// Relative to the bottom left corner of the rock.
+#[derive(Debug, Default, Clone, Copy)]
+struct RockPosIter {
+    n: usize,
+    positions: [Vec2; 5],
+}
+
+impl Iterator for RockPosIter {
+    type Item = Vec2;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.n.checked_sub(1) {
+            Some(n) => {
+                self.n = n;
+                Some(self.positions[n])
+            }
+            _ => None,
+        }
+    }
+}
+
+const ROCK_SHAPES: [&dyn RockShape; 5] = [
+    &Rock {
+        blocks: [[Block::Rock; 4]; 1],
+    },
+    &Rock {
+        blocks: [
+            [Block::Empty, Block::Rock, Block::Empty],
+            [Block::Rock, Block::Rock, Block::Rock],
+            [Block::Empty, Block::Rock, Block::Empty],
+        ],
+    },
+    &Rock {
+        blocks: [
+            [Block::Rock, Block::Rock, Block::Rock],
+            [Block::Empty, Block::Empty, Block::Rock],
+            [Block::Empty, Block::Empty, Block::Rock],
+        ],
+    },
+    &Rock {
+        blocks: [[Block::Rock; 1]; 4],
+    },
+    &Rock {
+        blocks: [[Block::Rock; 2]; 2],
+    },
+];
+
+#[derive(Debug)]
+struct Rock<const WIDTH: usize, const HEIGHT: usize> {
+    blocks: [[Block; WIDTH]; HEIGHT],
+}
+
+impl<const WIDTH: usize, const HEIGHT: usize> RockShape for Rock<WIDTH, HEIGHT> {
+    fn positions(&self) -> RockPosIter {
+        let mut out = RockPosIter::default();
+
+        let position_iter = (0..HEIGHT)
+            .rev()
+            .flat_map(|y| (0..WIDTH).rev().map(move |x| (x, y)))
+            .filter(|&(x, y)| self.blocks[y][x] == Block::Rock)
+            .enumerate();
+
+        for (i, (x, y)) in position_iter {
+            out.positions[i] = Vec2::new(x as i64, y as i64);
+            out.n = i + 1;
+        }
+
+        out
+    }
+}
+
+const ROCKS_TO_DROP: usize = 1_000_000_000_000;
+
+// Blocks of rocks to drop while looking for the tower to start repeating itself.
+const CYCLE_SEARCH_LIMIT: usize = 5000;
+
+pub struct Day17;
+
+impl Solution for Day17 {
+    fn day(&self) -> u8 {
+        17
+    }
+
+    fn title(&self) -> &'static str {
+        "Pyroclastic Flow"
+    }
+
+    fn parse(&self, input: &str) -> Result<(), SolveError> {
+        jet_pattern(input)?;
+        Ok(())
+    }
+
+    fn part1(&self, input: &str) -> Result<Answer, SolveError> {
+        part1(input)
+    }
+
+    fn part2(&self, input: &str) -> Result<Answer, SolveError> {
+        part2(input)
+    }
+}
+
+pub fn part1(input: &str) -> Result<Answer, SolveError> {
+    let jet_pattern = jet_pattern(input)?;
+
+    let mut jet_pattern_iter = jet_pattern.into_iter().cycle();
+    let mut rocks_iter = ROCK_SHAPES.into_iter().cycle();
+
+    let mut cave = Cave::<7>::new();
+
+    // Few enough rocks that there's no need to look for a cycle.
+    for _i in 0..2022 {
+        drop_rock(&mut cave, &mut rocks_iter, &mut jet_pattern_iter);
+    }
+
+    Ok(cave.height().into())
+}
+
+pub fn part2(input: &str) -> Result<Answer, SolveError> {
+    let jet_pattern = jet_pattern(input)?;
+    // After a block of this many rocks, the rocks and the jets are both back at the start of their
+    // patterns, so only the top of the cave can tell one block from another.
+    let increment = jet_pattern.len() * ROCK_SHAPES.len();
+
+    let mut jet_pattern_iter = jet_pattern.into_iter().cycle();
+    let mut rocks_iter = ROCK_SHAPES.into_iter().cycle();
+
+    let mut cave = Cave::<7>::new();
+
+    // The height of the tower after each rock.
+    let mut heights = vec![0];
+
+    let keys = (0..CYCLE_SEARCH_LIMIT).map(|_| {
+        let key = cave.cache_key();
+
+        for _i in 0..increment {
+            drop_rock(&mut cave, &mut rocks_iter, &mut jet_pattern_iter);
+            heights.push(cave.height() as i64);
+        }
+
+        key
+    });
+
+    let blocks = cycle::find_by_key(keys).ok_or(Error::NoCycle)?;
+    let rocks = Cycle {
+        start: blocks.start * increment,
+        length: blocks.length * increment,
+    };
+
+    Ok(rocks.extrapolate(&heights, ROCKS_TO_DROP).into())
+}
+
+// Never empty, so that it can be cycled through forever.
+fn jet_pattern(input: &str) -> Result<Vec<Direction>, Error> {
+    let jets = input
+        .trim_end()
+        .bytes()
+        .map(Direction::try_from)
+        .collect::<Result<Vec<_>, _>>()?;
+
+    if jets.is_empty() {
+        Err(Error::NoJets)
+    } else {
+        Ok(jets)
+    }
+}
+
+fn drop_rock<const WIDTH: usize, I0, I1>(
+    cave: &mut Cave<WIDTH>,
+    rocks_iter: &mut I0,
+    jet_pattern_iter: &mut I1,
+) where
+    I0: Iterator<Item = &'static dyn RockShape>,
+    I1: Iterator<Item = Direction>,
+{
+    let rock = rocks_iter.next().unwrap();
+    let mut pos = cave.rock_start_position();
+    loop {
+        let direction = jet_pattern_iter.next().unwrap();
+        let candidate = direction.apply(pos);
+
+        if cave.valid(rock, candidate) {
+            pos = candidate;
+        }
+
+        let candidate = pos - Vec2::new(0, 1);
+
+        if cave.valid(rock, candidate) {
+            pos = candidate;
+        } else {
+            for rock_pos in rock.positions() {
+                cave.put_rock(pos + rock_pos);
+            }
+
+            break;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rock_pos_iter() {
+        let rock = Rock {
+            blocks: [[Block::Rock; 4]; 1],
+        };
+
+        assert_eq!(
+            rock.positions().collect::<Vec<_>>(),
+            vec![
+                Vec2::new(0, 0),
+                Vec2::new(1, 0),
+                Vec2::new(2, 0),
+                Vec2::new(3, 0)
+            ]
+        );
+
+        let rock = Rock {
+            blocks: [[Block::Empty, Block::Rock], [Block::Rock, Block::Rock]],
+        };
+
+        assert_eq!(
+            rock.positions().collect::<Vec<_>>(),
+            vec![Vec2::new(1, 0), Vec2::new(0, 1), Vec2::new(1, 1),]
+        );
+    }
+
+    const EXAMPLE: &str = "\
+        >>><<><>><<<>><>>><<<>>><<<><<<>><>><<>>\n\
+    ";
+
+    #[test]
+    fn test_example_part1() {
+        assert_eq!(part1(EXAMPLE).unwrap(), Answer::Number(3068));
+    }
+
+    #[test]
+    fn test_example_part2() {
+        assert_eq!(part2(EXAMPLE).unwrap(), Answer::Number(1514285714288));
+    }
+}
@@ -0,0 +1,123 @@
+use nom::{
+    bytes::complete::tag,
+    character::complete::line_ending,
+    multi::separated_list1,
+    sequence::{terminated, tuple},
+    IResult, Parser,
+};
+
+use crate::geom::{Point3, Vec3};
+use crate::grid3::Grid3;
+use crate::parser::{base10_numeric, nom_parse_to_owned};
+use crate::solution::{Answer, Solution, SolveError};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Block {
+    Cube,
+    Air,
+}
+
+// Leaves a layer of air all the way around the droplet, so the steam can get to every side.
+// The cubes have already been moved one along each axis, so none are on the lower sides.
+fn scan(cubes: &[Point3]) -> Grid3<Block> {
+    let (max_x, max_y, max_z) = cubes
+        .iter()
+        .fold((0, 0, 0), |(max_x, max_y, max_z), &Point3 { x, y, z }| {
+            (max_x.max(x), max_y.max(y), max_z.max(z))
+        });
+
+    let dims = (max_x as usize + 2, max_y as usize + 2, max_z as usize + 2);
+    let mut grid = Grid3::new(dims, Block::Air);
+
+    for &Point3 { x, y, z } in cubes {
+        grid[(x as usize, y as usize, z as usize)] = Block::Cube;
+    }
+
+    grid
+}
+
+pub struct Day18;
+
+impl Solution for Day18 {
+    fn day(&self) -> u8 {
+        18
+    }
+
+    fn title(&self) -> &'static str {
+        "Boiling Boulders"
+    }
+
+    fn parse(&self, input: &str) -> Result<(), SolveError> {
+        nom_parse_to_owned(parse_input, input)?;
+        Ok(())
+    }
+
+    fn part1(&self, input: &str) -> Result<Answer, SolveError> {
+        part1(input)
+    }
+
+    fn part2(&self, input: &str) -> Result<Answer, SolveError> {
+        part2(input)
+    }
+}
+
+pub fn part1(input: &str) -> Result<Answer, SolveError> {
+    let grid = scan(&nom_parse_to_owned(parse_input, input)?);
+
+    Ok(grid.surface_area(|&b| b == Block::Cube).into())
+}
+
+pub fn part2(input: &str) -> Result<Answer, SolveError> {
+    let grid = scan(&nom_parse_to_owned(parse_input, input)?);
+
+    // Anything the steam can't get to might as well be lava, air pockets included.
+    let steam = grid.flood_fill([(0, 0, 0)], |&b| b == Block::Air);
+
+    Ok(steam.surface_area(|&reached| !reached).into())
+}
+
+fn parse_input(input: &str) -> IResult<&str, Vec<Point3>> {
+    separated_list1(
+        line_ending,
+        tuple((
+            terminated(base10_numeric, tag(",")),
+            terminated(base10_numeric, tag(",")),
+            base10_numeric,
+        ))
+        .map(|(x, y, z): (u32, u32, u32)| {
+            Point3::new(x.into(), y.into(), z.into()) + Vec3::new(1, 1, 1)
+        }),
+    )
+    .parse(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+        2,2,2\n\
+        1,2,2\n\
+        3,2,2\n\
+        2,1,2\n\
+        2,3,2\n\
+        2,2,1\n\
+        2,2,3\n\
+        2,2,4\n\
+        2,2,6\n\
+        1,2,5\n\
+        3,2,5\n\
+        2,1,5\n\
+        2,3,5\n\
+    ";
+
+    #[test]
+    fn test_example_part1() {
+        assert_eq!(part1(EXAMPLE).unwrap(), Answer::Number(64));
+    }
+
+    #[test]
+    fn test_example_part2() {
+        assert_eq!(part2(EXAMPLE).unwrap(), Answer::Number(58));
+    }
+}
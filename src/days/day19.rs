@@ -2,9 +2,11 @@
 
 use std::collections::BTreeMap;
 
-use aoc::parser::read_from_stdin_and_parse;
 use rayon::prelude::*;
 
+use crate::parser::nom_parse_to_owned;
+use crate::solution::{Answer, Solution, SolveError};
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 struct State<'a> {
     blueprint: &'a Blueprint,
@@ -17,11 +19,12 @@ struct State<'a> {
     obsidian_robot: u64,
     geode_robot: u64,
     time: u64,
+    max_time: u64,
     next_action: Action,
 }
 
 impl<'a> State<'a> {
-    fn initial_iterator(blueprint: &'a Blueprint) -> StateStepIter<'a> {
+    fn initial_iterator(blueprint: &'a Blueprint, max_time: u64) -> StateStepIter<'a> {
         let state = Self {
             blueprint,
             ore: 0,
@@ -33,6 +36,7 @@ impl<'a> State<'a> {
             obsidian_robot: 0,
             geode_robot: 0,
             time: 0,
+            max_time,
             next_action: Action::BuildGeodeRobot,
         };
 
@@ -124,7 +128,7 @@ impl<'a> State<'a> {
         let cost = self.blueprint.cost_of(action);
         let max_costs = self.max_costs();
 
-        let remaining_time = MAX_TIME - self.time;
+        let remaining_time = self.max_time - self.time;
 
         match action {
             Action::BuildOreRobot if self.ore_robot > max_costs.ore => return false,
@@ -140,7 +144,7 @@ impl<'a> State<'a> {
     }
 
     fn done(&self) -> bool {
-        self.time >= MAX_TIME
+        self.time >= self.max_time
     }
 }
 
@@ -219,6 +223,7 @@ impl<'a> Iterator for StateStepIter<'a> {
     }
 }
 
+#[allow(clippy::enum_variant_names)]
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum Action {
     BuildOreRobot,
@@ -272,29 +277,65 @@ impl Blueprint {
     }
 }
 
-pub fn main() -> Result<(), Box<dyn std::error::Error>> {
-    let mut blueprints = read_from_stdin_and_parse(parser::parse_input)?;
-    blueprints.truncate(3);
+pub struct Day19;
+
+impl Solution for Day19 {
+    fn day(&self) -> u8 {
+        19
+    }
+
+    fn title(&self) -> &'static str {
+        "Not Enough Minerals"
+    }
+
+    fn parse(&self, input: &str) -> Result<(), SolveError> {
+        nom_parse_to_owned(parser::parse_input, input)?;
+        Ok(())
+    }
+
+    fn part1(&self, input: &str) -> Result<Answer, SolveError> {
+        part1(input)
+    }
+
+    fn part2(&self, input: &str) -> Result<Answer, SolveError> {
+        part2(input)
+    }
+}
+
+pub fn part1(input: &str) -> Result<Answer, SolveError> {
+    let blueprints = nom_parse_to_owned(parser::parse_input, input)?;
 
     let mut best = BTreeMap::new();
 
     best.par_extend(
         blueprints
             .into_par_iter()
-            .map(|(i, blueprint)| (i, simulate(i, blueprint))),
+            .map(|(i, blueprint)| (i, simulate(blueprint, 24))),
     );
-    println!("{:?}", best);
 
-    let n = best.into_iter().map(|(_i, geode)| geode).product::<u64>();
-    println!("{}", n);
+    // The quality level of each blueprint is its id times the geodes it can open.
+    let n = best.into_iter().map(|(i, geodes)| i * geodes).sum::<u64>();
+    Ok(n.into())
+}
 
-    Ok(())
+pub fn part2(input: &str) -> Result<Answer, SolveError> {
+    let mut blueprints = nom_parse_to_owned(parser::parse_input, input)?;
+    blueprints.truncate(3);
+
+    let mut best = BTreeMap::new();
+
+    best.par_extend(
+        blueprints
+            .into_par_iter()
+            .map(|(i, blueprint)| (i, simulate(blueprint, 32))),
+    );
+
+    let n = best.into_values().product::<u64>();
+    Ok(n.into())
 }
 
-const MAX_TIME: u64 = 32;
-fn simulate(i: u64, blueprint: Blueprint) -> u64 {
-    println!("{}: {:?}", i, blueprint);
-    let mut current = State::initial_iterator(&blueprint).collect::<Vec<_>>();
+fn simulate(blueprint: Blueprint, max_time: u64) -> u64 {
+    let mut current = State::initial_iterator(&blueprint, max_time).collect::<Vec<_>>();
     let mut max = None;
 
     while let Some(mut state) = current.pop() {
@@ -313,14 +354,14 @@ fn simulate(i: u64, blueprint: Blueprint) -> u64 {
         current.extend(state.tick_until_action())
     }
 
-    println!("{}: done ({:?})", i, max);
-    max.unwrap()
+    // No way through to the end at all opens no geodes either.
+    max.unwrap_or(0)
 }
 
 mod parser {
     use super::*;
 
-    use aoc::parser::base10_numeric;
+    use crate::parser::base10_numeric;
     use nom::{
         branch::alt,
         bytes::complete::tag,
@@ -502,7 +543,7 @@ mod tests {
             },
         };
 
-        let mut state = State::initial_iterator(&blueprint).next().unwrap();
+        let mut state = State::initial_iterator(&blueprint, 24).next().unwrap();
 
         state.accrue();
         state.accrue();
@@ -542,7 +583,7 @@ mod tests {
             },
         };
 
-        let state = State::initial_iterator(&blueprint).next().unwrap();
+        let state = State::initial_iterator(&blueprint, 24).next().unwrap();
 
         assert_eq!(
             state.max_costs(),
@@ -553,4 +594,20 @@ mod tests {
             }
         );
     }
+
+    const EXAMPLE: &str = "\
+        Blueprint 1: Each ore robot costs 4 ore. Each clay robot costs 2 ore. Each obsidian robot costs 3 ore and 14 clay. Each geode robot costs 2 ore and 7 obsidian.\n\
+        Blueprint 2: Each ore robot costs 2 ore. Each clay robot costs 3 ore. Each obsidian robot costs 3 ore and 8 clay. Each geode robot costs 3 ore and 12 obsidian.\n\
+    ";
+
+    #[test]
+    fn test_example_part1() {
+        assert_eq!(part1(EXAMPLE).unwrap(), Answer::Number(33));
+    }
+
+    #[ignore = "slow"]
+    #[test]
+    fn test_example_part2() {
+        assert_eq!(part2(EXAMPLE).unwrap(), Answer::Number(3472));
+    }
 }
@@ -0,0 +1,246 @@
+// This started out as an exercise in building a somewhat efficient and idiomatic
+// container to do this rather than falling back to the "obvious" solution of
+// approximating it with a doubly linked list (well, the Rust equivalent).
+//
+// It paid off in step 2, this runs pretty quickly in release mode. The container
+// has since grown into `crate::collections::RelocationVec`.
+
+use nom::{character::complete::line_ending, multi::separated_list1, IResult, Parser};
+
+use crate::collections::RelocationVec;
+use crate::parser::{base10_numeric, nom_parse_to_owned};
+use crate::solution::{Answer, DayError, Solution, SolveError};
+
+#[derive(Debug)]
+enum Error {
+    TooShort(usize),
+    NoZero,
+}
+
+impl std::error::Error for Error {}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::TooShort(n) => write!(f, "need at least 2 numbers to mix, got {}", n),
+            Error::NoZero => f.write_str("no 0 to count the grove coordinates from"),
+        }
+    }
+}
+
+impl DayError for Error {
+    fn day(&self) -> u8 {
+        20
+    }
+}
+
+const KEY: i64 = 811589153;
+
+pub struct Day20;
+
+impl Solution for Day20 {
+    fn day(&self) -> u8 {
+        20
+    }
+
+    fn title(&self) -> &'static str {
+        "Grove Positioning System"
+    }
+
+    fn parse(&self, input: &str) -> Result<(), SolveError> {
+        nom_parse_to_owned(parse_input, input)?;
+        Ok(())
+    }
+
+    fn part1(&self, input: &str) -> Result<Answer, SolveError> {
+        part1(input)
+    }
+
+    fn part2(&self, input: &str) -> Result<Answer, SolveError> {
+        part2(input)
+    }
+}
+
+pub fn part1(input: &str) -> Result<Answer, SolveError> {
+    let vec = nom_parse_to_owned(parse_input, input)?;
+
+    Ok(decrypt(vec, 1, 1)?.into())
+}
+
+pub fn part2(input: &str) -> Result<Answer, SolveError> {
+    let vec = nom_parse_to_owned(parse_input, input)?;
+
+    Ok(decrypt(vec, KEY, 10)?.into())
+}
+
+fn decrypt(vec: Vec<i64>, key: i64, rounds: usize) -> Result<i64, Error> {
+    if vec.len() < 2 {
+        return Err(Error::TooShort(vec.len()));
+    }
+
+    if !vec.contains(&0) {
+        return Err(Error::NoZero);
+    }
+
+    let mut vec = vec
+        .into_iter()
+        .map(|i| i * key)
+        .collect::<RelocationVec<_>>();
+
+    for _ in 0..rounds {
+        mix(&mut vec, no_inspect);
+    }
+
+    let zero = vec.iter().position(|&value| value == 0).expect("checked");
+
+    let sum = (1..=3)
+        .map(|i| {
+            *vec.get((zero + i * 1000) % vec.len())
+                .expect("always a value")
+        })
+        .sum::<i64>();
+
+    Ok(sum)
+}
+
+fn no_inspect(_vec: &RelocationVec<i64>) {}
+
+// Moves everything in the order it started out in, which the ids follow.
+fn mix<F>(vec: &mut RelocationVec<i64>, mut debug_inspect: F)
+where
+    F: FnMut(&RelocationVec<i64>),
+{
+    // Inspect function used by the test cases.
+    debug_inspect(vec);
+
+    for id in 0..vec.len() {
+        let &value = vec.get_by_id(id).expect("nothing removed");
+        vec.move_by(id, value);
+
+        debug_inspect(vec);
+    }
+}
+
+fn parse_input(input: &str) -> IResult<&str, Vec<i64>> {
+    separated_list1(line_ending, base10_numeric).parse(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mix() {
+        let initial = vec![
+            8, 2, 32, -41, 6, 29, -4, 6, -8, 8, -3, -8, 3, -5, 0, -1, 2, 1, 10, -9,
+        ];
+
+        let mut vec = initial.iter().copied().collect::<RelocationVec<i64>>();
+
+        let mut steps = vec![];
+
+        mix(&mut vec, |vec| {
+            steps.push(vec.iter().copied().collect::<Vec<_>>())
+        });
+
+        // Test cases lifted from a Reddit comment.
+        let expected = vec![
+            vec![
+                8, 2, 32, -41, 6, 29, -4, 6, -8, 8, -3, -8, 3, -5, 0, -1, 2, 1, 10, -9,
+            ],
+            vec![
+                2, 32, -41, 6, 29, -4, 6, -8, 8, 8, -3, -8, 3, -5, 0, -1, 2, 1, 10, -9,
+            ],
+            vec![
+                32, -41, 2, 6, 29, -4, 6, -8, 8, 8, -3, -8, 3, -5, 0, -1, 2, 1, 10, -9,
+            ],
+            vec![
+                -41, 2, 6, 29, -4, 6, -8, 8, 8, -3, -8, 3, -5, 32, 0, -1, 2, 1, 10, -9,
+            ],
+            vec![
+                2, 6, 29, -4, 6, -8, 8, 8, -3, -8, 3, -5, 32, 0, -1, 2, -41, 1, 10, -9,
+            ],
+            vec![
+                2, 29, -4, 6, -8, 8, 8, 6, -3, -8, 3, -5, 32, 0, -1, 2, -41, 1, 10, -9,
+            ],
+            vec![
+                2, -4, 6, -8, 8, 8, 6, -3, -8, 3, -5, 29, 32, 0, -1, 2, -41, 1, 10, -9,
+            ],
+            vec![
+                2, 6, -8, 8, 8, 6, -3, -8, 3, -5, 29, 32, 0, -1, 2, -41, -4, 1, 10, -9,
+            ],
+            vec![
+                2, -8, 8, 8, 6, -3, -8, 6, 3, -5, 29, 32, 0, -1, 2, -41, -4, 1, 10, -9,
+            ],
+            vec![
+                2, 8, 8, 6, -3, -8, 6, 3, -5, 29, 32, 0, -8, -1, 2, -41, -4, 1, 10, -9,
+            ],
+            vec![
+                2, 8, 6, -3, -8, 6, 3, -5, 29, 32, 8, 0, -8, -1, 2, -41, -4, 1, 10, -9,
+            ],
+            vec![
+                2, 8, 6, -8, 6, 3, -5, 29, 32, 8, 0, -8, -1, 2, -41, -4, 1, 10, -9, -3,
+            ],
+            vec![
+                2, 8, 6, 6, 3, -5, 29, 32, 8, 0, -8, -1, 2, -41, -8, -4, 1, 10, -9, -3,
+            ],
+            vec![
+                2, 8, 6, 6, -5, 29, 32, 3, 8, 0, -8, -1, 2, -41, -8, -4, 1, 10, -9, -3,
+            ],
+            vec![
+                2, 8, 6, 6, 29, 32, 3, 8, 0, -8, -1, 2, -41, -8, -4, 1, 10, -9, -5, -3,
+            ],
+            vec![
+                2, 8, 6, 6, 29, 32, 3, 8, 0, -8, -1, 2, -41, -8, -4, 1, 10, -9, -5, -3,
+            ],
+            vec![
+                2, 8, 6, 6, 29, 32, 3, 8, 0, -1, -8, 2, -41, -8, -4, 1, 10, -9, -5, -3,
+            ],
+            vec![
+                2, 8, 6, 6, 29, 32, 3, 8, 0, -1, -8, -41, -8, 2, -4, 1, 10, -9, -5, -3,
+            ],
+            vec![
+                2, 8, 6, 6, 29, 32, 3, 8, 0, -1, -8, -41, -8, 2, -4, 10, 1, -9, -5, -3,
+            ],
+            vec![
+                2, 8, 6, 6, 29, 32, 10, 3, 8, 0, -1, -8, -41, -8, 2, -4, 1, -9, -5, -3,
+            ],
+            vec![
+                2, 8, 6, 6, 29, 32, 10, 3, -9, 8, 0, -1, -8, -41, -8, 2, -4, 1, -5, -3,
+            ],
+        ];
+
+        for (i, (step, expected)) in steps.iter().zip(expected).enumerate() {
+            assert_eq!(step, &expected, "step {}", i);
+        }
+
+        assert_eq!(
+            vec.iter().copied().collect::<Vec<_>>(),
+            vec![2, 8, 6, 6, 29, 32, 10, 3, -9, 8, 0, -1, -8, -41, -8, 2, -4, 1, -5, -3]
+        );
+
+        for (id, a) in initial.iter().enumerate() {
+            assert_eq!(vec.get_by_id(id), Some(a));
+        }
+    }
+
+    const EXAMPLE: &str = "\
+        1\n\
+        2\n\
+        -3\n\
+        3\n\
+        -2\n\
+        0\n\
+        4\n\
+    ";
+
+    #[test]
+    fn test_example_part1() {
+        assert_eq!(part1(EXAMPLE).unwrap(), Answer::Number(3));
+    }
+
+    #[test]
+    fn test_example_part2() {
+        assert_eq!(part2(EXAMPLE).unwrap(), Answer::Number(1623178306));
+    }
+}
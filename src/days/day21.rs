@@ -0,0 +1,536 @@
+use std::collections::HashMap;
+
+use crate::parser::nom_parse_to_owned;
+use crate::solution::{Answer, DayError, Solution, SolveError};
+
+use expr::{Expr, Rational};
+
+#[derive(Debug)]
+enum Error {
+    NoMonkey(String),
+    NotAnEquation(String),
+    UnknownMonkey(String),
+    DependsOnItself(String),
+    DivisionByZero,
+    Overflow,
+    NotLinear,
+    NoUniqueSolution,
+    NotWhole(Rational),
+}
+
+impl std::error::Error for Error {}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::NoMonkey(name) => write!(f, "no monkey {}", name),
+            Error::NotAnEquation(name) => {
+                write!(f, "monkey {} doesn't wait for two other monkeys", name)
+            }
+            Error::UnknownMonkey(name) => write!(f, "unknown monkey {}", name),
+            Error::DependsOnItself(name) => write!(f, "monkey {} waits for itself", name),
+            Error::DivisionByZero => f.write_str("division by zero"),
+            Error::Overflow => f.write_str("numbers too big to work with"),
+            Error::NotLinear => f.write_str("equation isn't linear in the unknown"),
+            Error::NoUniqueSolution => f.write_str("equation doesn't have exactly one solution"),
+            Error::NotWhole(n) => write!(f, "{} isn't a whole number", n),
+        }
+    }
+}
+
+impl DayError for Error {
+    fn day(&self) -> u8 {
+        21
+    }
+}
+
+#[derive(Debug)]
+enum Job {
+    Yell(i64),
+    Wait(String, Operation, String),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Operation {
+    Add,
+    Mul,
+    Div,
+    Sub,
+}
+
+impl std::fmt::Display for Operation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Operation::Add => "+",
+            Operation::Mul => "*",
+            Operation::Div => "/",
+            Operation::Sub => "-",
+        })
+    }
+}
+
+pub struct Day21;
+
+impl Solution for Day21 {
+    fn day(&self) -> u8 {
+        21
+    }
+
+    fn title(&self) -> &'static str {
+        "Monkey Math"
+    }
+
+    fn parse(&self, input: &str) -> Result<(), SolveError> {
+        nom_parse_to_owned(parser::parse_input, input)?;
+        Ok(())
+    }
+
+    fn part1(&self, input: &str) -> Result<Answer, SolveError> {
+        part1(input)
+    }
+
+    fn part2(&self, input: &str) -> Result<Answer, SolveError> {
+        part2(input)
+    }
+}
+
+const ROOT: &str = "root";
+const HUMN: &str = "humn";
+
+pub fn part1(input: &str) -> Result<Answer, SolveError> {
+    let monkeys = nom_parse_to_owned(parser::parse_input, input)?;
+
+    let value = build(&monkeys, ROOT, None)?.eval(&HashMap::new())?;
+
+    Ok(whole(value)?.into())
+}
+
+pub fn part2(input: &str) -> Result<Answer, SolveError> {
+    solve_for(input, ROOT, HUMN)
+}
+
+// What `unknown` has to yell for both sides of `equation` to match, whatever job `unknown` was
+// given. Only works when that comes down to a linear equation, as it does for the human.
+pub fn solve_for(input: &str, equation: &str, unknown: &str) -> Result<Answer, SolveError> {
+    let monkeys = nom_parse_to_owned(parser::parse_input, input)?;
+
+    let (a, b) = match monkeys.get(equation) {
+        Some(Job::Wait(a, _, b)) => (a, b),
+        Some(Job::Yell(_)) => return Err(Error::NotAnEquation(equation.to_owned()).into()),
+        None => return Err(Error::NoMonkey(equation.to_owned()).into()),
+    };
+
+    let lhs = build(&monkeys, a, Some(unknown))?;
+    let rhs = build(&monkeys, b, Some(unknown))?;
+
+    Ok(whole(Expr::solve(&lhs, &rhs, unknown)?)?.into())
+}
+
+// Everything `name` waits for, as one expression with the constant parts already worked out, and
+// `unknown` left as a variable.
+fn build(monkeys: &HashMap<String, Job>, name: &str, unknown: Option<&str>) -> Result<Expr, Error> {
+    if !monkeys.contains_key(name) {
+        return Err(Error::NoMonkey(name.to_owned()));
+    }
+
+    build_from(monkeys, name, unknown, &mut vec![])?.simplify()
+}
+
+// `waiting` is every monkey on the way down from the top, to catch one waiting for itself.
+fn build_from<'a>(
+    monkeys: &'a HashMap<String, Job>,
+    name: &'a str,
+    unknown: Option<&str>,
+    waiting: &mut Vec<&'a str>,
+) -> Result<Expr, Error> {
+    if unknown == Some(name) {
+        return Ok(Expr::Var(name.to_owned()));
+    }
+
+    match monkeys.get(name) {
+        Some(&Job::Yell(value)) => Ok(Expr::Const(Rational::from(value))),
+        Some(Job::Wait(a, operation, b)) => {
+            if waiting.contains(&name) {
+                return Err(Error::DependsOnItself(name.to_owned()));
+            }
+
+            waiting.push(name);
+            let a = build_from(monkeys, a, unknown, waiting)?;
+            let b = build_from(monkeys, b, unknown, waiting)?;
+            waiting.pop();
+
+            Ok(Expr::Op(*operation, Box::new(a), Box::new(b)))
+        }
+        None => Err(Error::UnknownMonkey(name.to_owned())),
+    }
+}
+
+fn whole(n: Rational) -> Result<i64, Error> {
+    n.to_integer().ok_or(Error::NotWhole(n))
+}
+
+mod expr {
+    use std::collections::HashMap;
+
+    use super::{Error, Operation};
+
+    // A fraction in its lowest terms, with the sign on top, so that it never loses anything to
+    // rounding the way integer division does.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub(super) struct Rational {
+        num: i128,
+        den: i128,
+    }
+
+    impl Rational {
+        pub(super) const ZERO: Rational = Rational { num: 0, den: 1 };
+        pub(super) const ONE: Rational = Rational { num: 1, den: 1 };
+
+        pub(super) fn new(num: i128, den: i128) -> Result<Rational, Error> {
+            if den == 0 {
+                return Err(Error::DivisionByZero);
+            }
+
+            let gcd = gcd(num, den);
+            let sign = den.signum();
+            Ok(Rational {
+                num: sign * (num / gcd),
+                den: sign * (den / gcd),
+            })
+        }
+
+        pub(super) fn to_integer(self) -> Option<i64> {
+            (self.den == 1).then(|| i64::try_from(self.num).ok())?
+        }
+
+        pub(super) fn apply(
+            self,
+            operation: Operation,
+            other: Rational,
+        ) -> Result<Rational, Error> {
+            let (a, b, c, d) = (self.num, self.den, other.num, other.den);
+
+            let (num, den) = match operation {
+                Operation::Add => (
+                    checked_cross(a, d, c, b, i128::checked_add),
+                    b.checked_mul(d),
+                ),
+                Operation::Sub => (
+                    checked_cross(a, d, c, b, i128::checked_sub),
+                    b.checked_mul(d),
+                ),
+                Operation::Mul => (a.checked_mul(c), b.checked_mul(d)),
+                Operation::Div => (a.checked_mul(d), b.checked_mul(c)),
+            };
+
+            Rational::new(num.ok_or(Error::Overflow)?, den.ok_or(Error::Overflow)?)
+        }
+    }
+
+    impl From<i64> for Rational {
+        fn from(n: i64) -> Rational {
+            Rational {
+                num: n.into(),
+                den: 1,
+            }
+        }
+    }
+
+    impl std::fmt::Display for Rational {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            if self.den == 1 {
+                write!(f, "{}", self.num)
+            } else {
+                write!(f, "{}/{}", self.num, self.den)
+            }
+        }
+    }
+
+    // `a * b` and `c * d`, combined with `f`.
+    fn checked_cross(
+        a: i128,
+        b: i128,
+        c: i128,
+        d: i128,
+        f: fn(i128, i128) -> Option<i128>,
+    ) -> Option<i128> {
+        f(a.checked_mul(b)?, c.checked_mul(d)?)
+    }
+
+    fn gcd(a: i128, b: i128) -> i128 {
+        let (mut a, mut b) = (a.abs(), b.abs());
+        while b != 0 {
+            (a, b) = (b, a % b);
+        }
+        a
+    }
+
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub(super) enum Expr {
+        Const(Rational),
+        Var(String),
+        Op(Operation, Box<Expr>, Box<Expr>),
+    }
+
+    // `coeff * x + constant`, for some variable `x`.
+    #[derive(Debug, Clone, Copy)]
+    struct Linear {
+        coeff: Rational,
+        constant: Rational,
+    }
+
+    impl Expr {
+        // Works out everything that doesn't depend on a variable, and drops anything that can't
+        // change the answer, like adding 0 or multiplying by 1.
+        pub(super) fn simplify(self) -> Result<Expr, Error> {
+            use Operation::*;
+
+            let Expr::Op(operation, a, b) = self else {
+                return Ok(self);
+            };
+
+            let out = match (operation, a.simplify()?, b.simplify()?) {
+                (_, Expr::Const(a), Expr::Const(b)) => Expr::Const(a.apply(operation, b)?),
+                (Add, Expr::Const(Rational::ZERO), e)
+                | (Add | Sub, e, Expr::Const(Rational::ZERO))
+                | (Mul, Expr::Const(Rational::ONE), e)
+                | (Mul | Div, e, Expr::Const(Rational::ONE)) => e,
+                (Mul, Expr::Const(Rational::ZERO), _) | (Mul, _, Expr::Const(Rational::ZERO)) => {
+                    Expr::Const(Rational::ZERO)
+                }
+                (Div, _, Expr::Const(Rational::ZERO)) => return Err(Error::DivisionByZero),
+                (_, a, b) => Expr::Op(operation, Box::new(a), Box::new(b)),
+            };
+
+            Ok(out)
+        }
+
+        // Every variable has to be given a value.
+        pub(super) fn eval(&self, vars: &HashMap<String, Rational>) -> Result<Rational, Error> {
+            match self {
+                Expr::Const(n) => Ok(*n),
+                Expr::Var(name) => vars
+                    .get(name)
+                    .copied()
+                    .ok_or_else(|| Error::UnknownMonkey(name.clone())),
+                Expr::Op(operation, a, b) => a.eval(vars)?.apply(*operation, b.eval(vars)?),
+            }
+        }
+
+        // The value of `var` that makes both sides the same. `var` can be on either side, or
+        // both, as long as neither multiplies it by itself or divides by it.
+        pub(super) fn solve(lhs: &Expr, rhs: &Expr, var: &str) -> Result<Rational, Error> {
+            let (lhs, rhs) = (lhs.linear(var)?, rhs.linear(var)?);
+
+            // a x + b = c x + d, so x = (d - b) / (a - c).
+            let coeff = lhs.coeff.apply(Operation::Sub, rhs.coeff)?;
+            if coeff == Rational::ZERO {
+                return Err(Error::NoUniqueSolution);
+            }
+
+            rhs.constant
+                .apply(Operation::Sub, lhs.constant)?
+                .apply(Operation::Div, coeff)
+        }
+
+        fn linear(&self, var: &str) -> Result<Linear, Error> {
+            use Operation::*;
+
+            let out = match self {
+                Expr::Const(n) => Linear {
+                    coeff: Rational::ZERO,
+                    constant: *n,
+                },
+                Expr::Var(name) if name == var => Linear {
+                    coeff: Rational::ONE,
+                    constant: Rational::ZERO,
+                },
+                Expr::Var(name) => return Err(Error::UnknownMonkey(name.clone())),
+                Expr::Op(operation, a, b) => {
+                    let (a, b) = (a.linear(var)?, b.linear(var)?);
+
+                    match operation {
+                        Add | Sub => Linear {
+                            coeff: a.coeff.apply(*operation, b.coeff)?,
+                            constant: a.constant.apply(*operation, b.constant)?,
+                        },
+                        // One side or the other has to be a constant.
+                        Mul if a.coeff == Rational::ZERO => b.scale(Mul, a.constant)?,
+                        Mul if b.coeff == Rational::ZERO => a.scale(Mul, b.constant)?,
+                        Div if b.coeff == Rational::ZERO => a.scale(Div, b.constant)?,
+                        Mul | Div => return Err(Error::NotLinear),
+                    }
+                }
+            };
+
+            Ok(out)
+        }
+    }
+
+    impl Linear {
+        fn scale(self, operation: Operation, by: Rational) -> Result<Linear, Error> {
+            Ok(Linear {
+                coeff: self.coeff.apply(operation, by)?,
+                constant: self.constant.apply(operation, by)?,
+            })
+        }
+    }
+
+    // Fully bracketed, so there's no need to worry about precedence.
+    impl std::fmt::Display for Expr {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            match self {
+                Expr::Const(n) => write!(f, "{}", n),
+                Expr::Var(name) => f.write_str(name),
+                Expr::Op(operation, a, b) => write!(f, "({} {} {})", a, operation, b),
+            }
+        }
+    }
+}
+
+mod parser {
+    use super::*;
+    use crate::parser::base10_numeric;
+    use nom::{
+        branch::alt,
+        bytes::complete::tag,
+        character::complete::{alphanumeric1, line_ending, space1},
+        combinator::map,
+        multi::separated_list1,
+        sequence::{preceded, terminated, tuple},
+        IResult, Parser,
+    };
+
+    fn operator(input: &str) -> IResult<&str, Operation> {
+        alt((
+            map(tag("+"), |_| Operation::Add),
+            map(tag("*"), |_| Operation::Mul),
+            map(tag("/"), |_| Operation::Div),
+            map(tag("-"), |_| Operation::Sub),
+        ))
+        .parse(input)
+    }
+
+    fn name(input: &str) -> IResult<&str, String> {
+        map(alphanumeric1, str::to_owned).parse(input)
+    }
+
+    fn operation(input: &str) -> IResult<&str, (String, Operation, String)> {
+        tuple((terminated(name, space1), operator, preceded(space1, name))).parse(input)
+    }
+
+    fn monkey(input: &str) -> IResult<&str, (String, Job)> {
+        tuple((
+            terminated(name, tag(": ")),
+            alt((
+                base10_numeric.map(Job::Yell),
+                operation.map(|(a, op, b)| Job::Wait(a, op, b)),
+            )),
+        ))
+        .parse(input)
+    }
+
+    pub(super) fn parse_input(input: &str) -> IResult<&str, HashMap<String, Job>> {
+        separated_list1(line_ending, monkey)
+            .map(|monkeys| monkeys.into_iter().collect())
+            .parse(input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+        root: pppw + sjmn\n\
+        dbpl: 5\n\
+        cczh: sllz + lgvd\n\
+        zczc: 2\n\
+        ptdq: humn - dvpt\n\
+        dvpt: 3\n\
+        lfqf: 4\n\
+        humn: 5\n\
+        ljgn: 2\n\
+        sjmn: drzm * dbpl\n\
+        sllz: 4\n\
+        pppw: cczh / lfqf\n\
+        lgvd: ljgn * ptdq\n\
+        drzm: hmdt - zczc\n\
+        hmdt: 32";
+
+    #[test]
+    fn test_example_part1() {
+        assert_eq!(part1(EXAMPLE).unwrap(), Answer::Number(152));
+    }
+
+    #[test]
+    fn test_example_part2() {
+        assert_eq!(part2(EXAMPLE).unwrap(), Answer::Number(301));
+    }
+
+    #[test]
+    fn test_solve_for_anyone() {
+        // (4 + 2 * (5 - dvpt)) / 4 = 150
+        assert_eq!(
+            solve_for(EXAMPLE, ROOT, "dvpt").unwrap(),
+            Answer::Number(-293)
+        );
+        // Any monkey can stand in for root: 4 + 2 * (humn - 3) = 4.
+        assert_eq!(solve_for(EXAMPLE, "pppw", HUMN).unwrap(), Answer::Number(3));
+        // Neither side depends on the human.
+        assert_eq!(
+            solve_for(EXAMPLE, "sjmn", HUMN).unwrap_err().to_string(),
+            "equation doesn't have exactly one solution"
+        );
+        assert_eq!(
+            solve_for("root: sqre + four\nsqre: humn * humn\nfour: 4", ROOT, HUMN)
+                .unwrap_err()
+                .to_string(),
+            "equation isn't linear in the unknown"
+        );
+
+        // The human on both sides: humn / 3 = humn - 4.
+        let input = "root: abcd + efgh\nabcd: humn / three\nefgh: humn - ijkl\nthree: 3\nijkl: 4";
+        assert_eq!(solve_for(input, ROOT, HUMN).unwrap(), Answer::Number(6));
+    }
+
+    #[test]
+    fn test_expr() {
+        let x = || Box::new(Expr::Var("x".to_owned()));
+        let n = |n| Box::new(Expr::Const(Rational::from(n)));
+
+        // ((x * 1) + (3 / 2)) - (0 * x)
+        let expr = Expr::Op(
+            Operation::Sub,
+            Box::new(Expr::Op(
+                Operation::Add,
+                Box::new(Expr::Op(Operation::Mul, x(), n(1))),
+                Box::new(Expr::Op(Operation::Div, n(3), n(2))),
+            )),
+            Box::new(Expr::Op(Operation::Mul, n(0), x())),
+        );
+
+        let simplified = expr.clone().simplify().unwrap();
+        assert_eq!(simplified.to_string(), "(x + 3/2)");
+
+        let vars = HashMap::from([("x".to_owned(), Rational::new(1, -2).unwrap())]);
+        assert_eq!(expr.eval(&vars).unwrap(), Rational::ONE);
+        assert_eq!(simplified.eval(&vars).unwrap(), Rational::ONE);
+
+        assert_eq!(
+            Expr::solve(&simplified, &Expr::Const(Rational::ZERO), "x").unwrap(),
+            Rational::new(-3, 2).unwrap()
+        );
+        assert!(matches!(
+            Expr::Op(Operation::Div, x(), n(0)).simplify(),
+            Err(Error::DivisionByZero)
+        ));
+    }
+
+    #[test]
+    fn test_unknown_monkey() {
+        let e = part1("root: abcd + humn\nhumn: 5").unwrap_err();
+        assert_eq!(e.day(), Some(21));
+        assert_eq!(e.to_string(), "unknown monkey abcd");
+    }
+}
@@ -1,8 +1,41 @@
 use std::collections::{BTreeSet, HashMap};
 
-use aoc::parser::read_from_stdin_and_parse;
 use itertools::Itertools;
 
+use crate::geom::{Dir4, Point2, Vec2};
+use crate::grid::Grid;
+use crate::parser::nom_parse_to_owned;
+use crate::solution::{Answer, DayError, Solution, SolveError};
+
+#[derive(Debug)]
+enum Error {
+    NoOpenTile,
+    CubeLayout(&'static str),
+    NotOnCube(Point2),
+    MissingEdge(CubeSide, Facing),
+}
+
+impl std::error::Error for Error {}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::NoOpenTile => f.write_str("no open tile in the top row to start on"),
+            Error::CubeLayout(message) => write!(f, "{}", message),
+            Error::NotOnCube(pos) => write!(f, "{:?} isn't on any face of the cube", pos),
+            Error::MissingEdge(side, facing) => {
+                write!(f, "nowhere to go from face {:?} heading {:?}", side, facing)
+            }
+        }
+    }
+}
+
+impl DayError for Error {
+    fn day(&self) -> u8 {
+        22
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum Tile {
     Void,
@@ -23,12 +56,12 @@ enum CubeSide {
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 struct Region {
-    x0y0: Position,
-    xnyn: Position,
+    x0y0: Point2,
+    xnyn: Point2,
 }
 
 impl Region {
-    fn contains(&self, pos: Position) -> bool {
+    fn contains(&self, pos: Point2) -> bool {
         self.x0y0.x <= pos.x && self.xnyn.x >= pos.x && self.x0y0.y <= pos.y && self.xnyn.y >= pos.y
     }
 }
@@ -46,42 +79,40 @@ struct CubeLayout {
 }
 
 impl CubeLayout {
-    fn infer(vec: &Vec<Vec<Tile>>) -> Result<CubeLayout, &'static str> {
+    fn infer(grid: &Grid<Tile>) -> Result<CubeLayout, &'static str> {
         // I'm sure there's a better way to do this. I fumbled my way through
         // this intuitively and didn't look for a better algorithm.
 
-        let all_lens = vec.iter().map(|row| row.len()).collect::<BTreeSet<_>>();
-
-        let max_width = *all_lens.iter().max().ok_or("no lengths")?;
-        let dimension = max_width.max(vec.len()) / 4;
+        let max_width = grid.width();
+        let dimension = max_width.max(grid.height()) / 4;
+        if dimension == 0 {
+            return Err("map is too small to fold into a cube");
+        }
 
-        for len in std::iter::once(vec.len()).chain(all_lens.iter().copied()) {
-            if len % dimension != 0 {
-                return Err("unable to infer dimension");
-            }
+        if !max_width.is_multiple_of(dimension) || !grid.height().is_multiple_of(dimension) {
+            return Err("unable to infer dimension");
         }
 
         // Split out the "real" regions from the void regions, we
         // actually don't need to know about the void regions.
-        let (void_regions, cube_regions) = (0..vec.len() / dimension)
+        let (void_regions, cube_regions) = (0..grid.height() / dimension)
             .flat_map(|big_y| {
                 (0..max_width / dimension).map(move |big_x| {
-                    let x0y0 = Position {
-                        x: big_x as isize * dimension as isize,
-                        y: big_y as isize * dimension as isize,
+                    let x0y0 = Point2 {
+                        x: big_x as i64 * dimension as i64,
+                        y: big_y as i64 * dimension as i64,
                     };
 
-                    let xnyn = Position {
-                        x: x0y0.x + dimension as isize - 1,
-                        y: x0y0.y + dimension as isize - 1,
+                    let xnyn = Point2 {
+                        x: x0y0.x + dimension as i64 - 1,
+                        y: x0y0.y + dimension as i64 - 1,
                     };
 
                     Region { x0y0, xnyn }
                 })
             })
             .partition::<Vec<_>, _>(|region| {
-                vec.get(region.x0y0.y as usize)
-                    .and_then(|row| row.get(region.x0y0.x as usize))
+                grid.get((region.x0y0.x as usize, region.x0y0.y as usize))
                     .copied()
                     .unwrap_or(Tile::Void)
                     == Tile::Void
@@ -95,7 +126,7 @@ impl CubeLayout {
             .into_group_map_by(|r| r.x0y0.x)
             .into_values()
             .max_by_key(Vec::len)
-            .expect("couldn't find longest line");
+            .ok_or("couldn't find longest line")?;
 
         longest_vertical_line.sort_by_key(|r| r.x0y0.y);
 
@@ -132,7 +163,7 @@ impl CubeLayout {
             .copied()
             .filter(|r| {
                 ys.contains(&r.x0y0.y)
-                    && r.x0y0.x == regions[&CubeSide::D].x0y0.x - dimension as isize
+                    && r.x0y0.x == regions[&CubeSide::D].x0y0.x - dimension as i64
             })
             .collect::<Vec<_>>();
 
@@ -146,7 +177,7 @@ impl CubeLayout {
             .copied()
             .filter(|r| {
                 ys.contains(&r.x0y0.y)
-                    && r.x0y0.x == regions[&CubeSide::D].x0y0.x + dimension as isize
+                    && r.x0y0.x == regions[&CubeSide::D].x0y0.x + dimension as i64
             })
             .collect::<Vec<_>>();
 
@@ -195,17 +226,17 @@ impl CubeLayout {
             .filter_map(|((&ka, &a), (&kb, &b))| {
                 // Figure out which edges are connected
                 if a.x0y0.x == b.x0y0.x {
-                    if a.x0y0.y == b.x0y0.y + dimension as isize {
+                    if a.x0y0.y == b.x0y0.y + dimension as i64 {
                         Some(((ka, Facing::North), (kb, Facing::South)))
-                    } else if a.x0y0.y + dimension as isize == b.x0y0.y {
+                    } else if a.x0y0.y + dimension as i64 == b.x0y0.y {
                         Some(((ka, Facing::South), (kb, Facing::North)))
                     } else {
                         None
                     }
                 } else if a.x0y0.y == b.x0y0.y {
-                    if a.x0y0.x == b.x0y0.x + dimension as isize {
+                    if a.x0y0.x == b.x0y0.x + dimension as i64 {
                         Some(((ka, Facing::West), (kb, Facing::East)))
-                    } else if a.x0y0.x + dimension as isize == b.x0y0.x {
+                    } else if a.x0y0.x + dimension as i64 == b.x0y0.x {
                         Some(((ka, Facing::East), (kb, Facing::West)))
                     } else {
                         None
@@ -280,33 +311,27 @@ impl CubeLayout {
         })
     }
 
-    fn next_position(&self, position: Position, facing: Facing) -> (Position, Facing) {
+    // `infer` has checked every face has all its edges, so the errors here can only come from
+    // walking off a layout that got past it somehow.
+    fn next_position(&self, position: Point2, facing: Facing) -> Result<(Point2, Facing), Error> {
         let (&current_side, &current_region) = self
             .regions
             .iter()
             .find(|(_, region)| region.contains(position))
-            .unwrap();
+            .ok_or(Error::NotOnCube(position))?;
 
         // Step, and wrap around if we hit the edge.
-        let (x, y) = match facing {
-            Facing::North => (position.x, position.y - 1),
-            Facing::East => (position.x + 1, position.y),
-            Facing::South => (position.x, position.y + 1),
-            Facing::West => (position.x - 1, position.y),
-        };
-
-        let pos = Position { x, y };
+        let pos = position + facing.vec();
 
         if current_region.contains(pos) {
-            return (pos, facing);
+            return Ok((pos, facing));
         }
 
-        let (new_side, new_side_edge) = self
+        let (new_side_edge, new_region) = self
             .edges
             .get(&(current_side, facing))
-            .unwrap_or_else(|| panic!("no edge for {:?} {:?}", current_region, facing));
-
-        let new_region = self.regions.get(new_side).unwrap();
+            .and_then(|(new_side, edge)| Some((edge, self.regions.get(new_side)?)))
+            .ok_or(Error::MissingEdge(current_side, facing))?;
 
         let offset = match facing {
             Facing::North => position.x - current_region.x0y0.x,
@@ -315,84 +340,120 @@ impl CubeLayout {
             Facing::West => current_region.xnyn.y - position.y,
         };
 
-        match new_side_edge {
+        let next = match new_side_edge {
             Facing::North => (
-                Position {
+                Point2 {
                     x: new_region.xnyn.x - offset,
                     y: new_region.x0y0.y,
                 },
                 Facing::South,
             ),
             Facing::East => (
-                Position {
+                Point2 {
                     x: new_region.xnyn.x,
                     y: new_region.xnyn.y - offset,
                 },
                 Facing::West,
             ),
             Facing::South => (
-                Position {
+                Point2 {
                     x: new_region.x0y0.x + offset,
                     y: new_region.xnyn.y,
                 },
                 Facing::North,
             ),
             Facing::West => (
-                Position {
+                Point2 {
                     x: new_region.x0y0.x,
                     y: new_region.x0y0.y + offset,
                 },
                 Facing::East,
             ),
-        }
+        };
+
+        Ok(next)
     }
 }
 
+// How to carry on when walking off the edge of the map.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Wrapping {
+    // Come back in on the opposite side of the same row/column.
+    Flat,
+    // Fold the map into a cube and walk onto the adjoining face.
+    Cube,
+}
+
 #[derive(Debug, PartialEq, Eq)]
 struct Map {
-    map: Vec<Vec<Tile>>,
+    map: Grid<Tile>,
     cube_layout: CubeLayout,
+    start: Point2,
 }
 
 impl Map {
-    fn new(map: Vec<Vec<Tile>>) -> Self {
-        let cube_layout = CubeLayout::infer(&map).expect("unable to infer cube layout");
-        Self { map, cube_layout }
-    }
+    // The lines of the map can be different lengths, anything past the end is void.
+    fn new(rows: Vec<Vec<Tile>>) -> Result<Self, Error> {
+        let map = Grid::from_rows_padded(rows, Tile::Void);
+        let cube_layout = CubeLayout::infer(&map).map_err(Error::CubeLayout)?;
 
-    fn initial_position(&self) -> Position {
         // First ground tile in the first row
-        let x = self
+        let x = map
+            .rows()
+            .next()
+            .and_then(|row| row.iter().position(|&tile| tile == Tile::Ground))
+            .ok_or(Error::NoOpenTile)? as i64;
+
+        Ok(Self {
+            map,
+            cube_layout,
+            start: Point2 { x, y: 0 },
+        })
+    }
+
+    fn initial_position(&self) -> Point2 {
+        self.start
+    }
+
+    // Step once, wrapping around the bounding box of the map. This will often land in the void,
+    // which is skipped over by `advance_position`.
+    fn flat_next_position(&self, position: Point2, facing: Facing) -> (Point2, Facing) {
+        let (x, y) = self
             .map
-            .get(0)
-            .unwrap()
-            .iter()
-            .position(|&tile| tile == Tile::Ground)
-            .unwrap() as isize;
+            .wrapping_offset((position.x as usize, position.y as usize), facing.vec());
 
-        Position { x, y: 0 }
+        (
+            Point2 {
+                x: x as i64,
+                y: y as i64,
+            },
+            facing,
+        )
     }
 
     fn advance_position(
         &self,
-        original_position: Position,
+        original_position: Point2,
         original_facing: Facing,
-    ) -> (Position, Facing) {
+        wrapping: Wrapping,
+    ) -> Result<(Point2, Facing), Error> {
         // Iterate "next_position" until we hit a ground or wall. If it's a wall
         // we return the original position, if it's ground we return that position.
         let mut position = original_position;
         let mut facing = original_facing;
         loop {
-            let (next_position, next_facing) = self.cube_layout.next_position(position, facing);
+            let (next_position, next_facing) = match wrapping {
+                Wrapping::Flat => self.flat_next_position(position, facing),
+                Wrapping::Cube => self.cube_layout.next_position(position, facing)?,
+            };
 
             match self
                 .map
-                .get(next_position.y as usize)
-                .and_then(|row| row.get(next_position.x as usize))
+                .get((next_position.x as usize, next_position.y as usize))
                 .unwrap_or(&Tile::Void)
             {
-                Tile::Ground => return (next_position, next_facing),
-                Tile::Wall => return (original_position, original_facing),
+                Tile::Ground => return Ok((next_position, next_facing)),
+                Tile::Wall => return Ok((original_position, original_facing)),
                 Tile::Void => (position, facing) = (next_position, next_facing),
             }
         }
@@ -415,6 +476,19 @@ enum Facing {
     North = 3,
 }
 
+impl Facing {
+    fn vec(self) -> Vec2 {
+        let dir = match self {
+            Facing::North => Dir4::North,
+            Facing::East => Dir4::East,
+            Facing::South => Dir4::South,
+            Facing::West => Dir4::West,
+        };
+
+        dir.vec()
+    }
+}
+
 impl std::ops::Add<Instruction> for Facing {
     type Output = Self;
 
@@ -437,15 +511,9 @@ impl std::ops::Add<Instruction> for Facing {
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
-struct Position {
-    x: isize,
-    y: isize,
-}
-
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 struct State {
-    position: Position,
+    position: Point2,
     facing: Facing,
 }
 
@@ -458,26 +526,64 @@ impl State {
     }
 }
 
-pub fn main() {
-    let (map, instructions) = read_from_stdin_and_parse(parser::parse_input).unwrap();
+pub struct Day22;
+
+impl Solution for Day22 {
+    fn day(&self) -> u8 {
+        22
+    }
+
+    fn title(&self) -> &'static str {
+        "Monkey Map"
+    }
+
+    fn parse(&self, input: &str) -> Result<(), SolveError> {
+        parse_notes(input)?;
+        Ok(())
+    }
 
-    CubeLayout::infer(&map.map).unwrap();
+    fn part1(&self, input: &str) -> Result<Answer, SolveError> {
+        part1(input)
+    }
+
+    fn part2(&self, input: &str) -> Result<Answer, SolveError> {
+        part2(input)
+    }
+}
 
-    let mut state = State::initial(&map);
+pub fn part1(input: &str) -> Result<Answer, SolveError> {
+    let (map, instructions) = parse_notes(input)?;
+
+    Ok(password(&map, instructions, Wrapping::Flat)?.into())
+}
+
+pub fn part2(input: &str) -> Result<Answer, SolveError> {
+    let (map, instructions) = parse_notes(input)?;
+
+    Ok(password(&map, instructions, Wrapping::Cube)?.into())
+}
+
+fn parse_notes(input: &str) -> Result<(Map, Vec<Instruction>), SolveError> {
+    let (tiles, instructions) = nom_parse_to_owned(parser::parse_input, input)?;
+
+    Ok((Map::new(tiles)?, instructions))
+}
+
+fn password(map: &Map, instructions: Vec<Instruction>, wrapping: Wrapping) -> Result<i64, Error> {
+    let mut state = State::initial(map);
     for instruction in instructions {
         match instruction {
             Instruction::Move(distance) => {
                 for _ in 0..distance {
                     (state.position, state.facing) =
-                        map.advance_position(state.position, state.facing);
+                        map.advance_position(state.position, state.facing, wrapping)?;
                 }
             }
             _ => state.facing = state.facing + instruction,
         }
     }
 
-    let n = 4 * (state.position.x + 1) + 1000 * (state.position.y + 1) + state.facing as isize;
-    println!("{:?} - {}", state, n);
+    Ok(4 * (state.position.x + 1) + 1000 * (state.position.y + 1) + state.facing as i64)
 }
 
 #[cfg(test)]
@@ -497,52 +603,52 @@ mod tests {
     ..
 
 0R5R2R8L1"
-            .strip_prefix("\n")
+            .strip_prefix('\n')
             .unwrap();
 
-        let (map, instructions) = parser::parse_input(input).unwrap().1;
+        let (map, instructions) = parse_notes(input).unwrap();
 
         let expected = [
             (
                 CubeSide::D,
                 Region {
-                    x0y0: Position { x: 2, y: 0 },
-                    xnyn: Position { x: 3, y: 1 },
+                    x0y0: Point2 { x: 2, y: 0 },
+                    xnyn: Point2 { x: 3, y: 1 },
                 },
             ),
             (
                 CubeSide::F,
                 Region {
-                    x0y0: Position { x: 2, y: 2 },
-                    xnyn: Position { x: 3, y: 3 },
+                    x0y0: Point2 { x: 2, y: 2 },
+                    xnyn: Point2 { x: 3, y: 3 },
                 },
             ),
             (
                 CubeSide::U,
                 Region {
-                    x0y0: Position { x: 2, y: 4 },
-                    xnyn: Position { x: 3, y: 5 },
+                    x0y0: Point2 { x: 2, y: 4 },
+                    xnyn: Point2 { x: 3, y: 5 },
                 },
             ),
             (
                 CubeSide::L,
                 Region {
-                    x0y0: Position { x: 0, y: 2 },
-                    xnyn: Position { x: 1, y: 3 },
+                    x0y0: Point2 { x: 0, y: 2 },
+                    xnyn: Point2 { x: 1, y: 3 },
                 },
             ),
             (
                 CubeSide::R,
                 Region {
-                    x0y0: Position { x: 4, y: 4 },
-                    xnyn: Position { x: 5, y: 5 },
+                    x0y0: Point2 { x: 4, y: 4 },
+                    xnyn: Point2 { x: 5, y: 5 },
                 },
             ),
             (
                 CubeSide::B,
                 Region {
-                    x0y0: Position { x: 4, y: 6 },
-                    xnyn: Position { x: 5, y: 7 },
+                    x0y0: Point2 { x: 4, y: 6 },
+                    xnyn: Point2 { x: 5, y: 7 },
                 },
             ),
         ]
@@ -591,29 +697,69 @@ mod tests {
 
         let mut state = State::initial(&map);
 
-        assert_eq!(state.position, Position { x: 2, y: 0 });
+        assert_eq!(state.position, Point2 { x: 2, y: 0 });
         assert_eq!(state.facing, Facing::East);
 
         for instruction in instructions {
             match instruction {
                 Instruction::Move(distance) => {
                     for _ in 0..distance {
-                        (state.position, state.facing) =
-                            map.advance_position(state.position, state.facing);
+                        (state.position, state.facing) = map
+                            .advance_position(state.position, state.facing, Wrapping::Cube)
+                            .unwrap();
                     }
                 }
                 _ => state.facing = state.facing + instruction,
             }
         }
 
-        assert_eq!(state.position, Position { x: 2, y: 0 });
+        assert_eq!(state.position, Point2 { x: 2, y: 0 });
         assert_eq!(state.facing, Facing::East);
+
+        // Walking off an edge that isn't there is an error, not a panic.
+        let mut layout = map.cube_layout;
+        layout.edges.remove(&(CubeSide::D, Facing::North));
+        assert!(matches!(
+            layout.next_position(Point2 { x: 2, y: 0 }, Facing::North),
+            Err(Error::MissingEdge(CubeSide::D, Facing::North))
+        ));
+        assert!(matches!(
+            layout.next_position(Point2 { x: 0, y: 0 }, Facing::North),
+            Err(Error::NotOnCube(_))
+        ));
+    }
+
+    const EXAMPLE: &str = concat!(
+        "        ...#\n",
+        "        .#..\n",
+        "        #...\n",
+        "        ....\n",
+        "...#.......#\n",
+        "........#...\n",
+        "..#....#....\n",
+        "..........#.\n",
+        "        ...#....\n",
+        "        .....#..\n",
+        "        .#......\n",
+        "        ......#.\n",
+        "\n",
+        "10R5L5R10L4R5L5\n",
+    );
+
+    #[test]
+    fn test_example_part1() {
+        assert_eq!(part1(EXAMPLE).unwrap(), Answer::Number(6032));
+    }
+
+    #[test]
+    fn test_example_part2() {
+        assert_eq!(part2(EXAMPLE).unwrap(), Answer::Number(5031));
     }
 }
 
 mod parser {
     use super::*;
-    use aoc::parser::base10_numeric;
+    use crate::parser::base10_numeric;
     use nom::{
         branch::alt,
         bytes::complete::tag,
@@ -633,10 +779,8 @@ mod parser {
         .parse(input)
     }
 
-    fn monkey_map(input: &str) -> IResult<&str, Map> {
-        separated_list1(line_ending, many1(tile))
-            .map(Map::new)
-            .parse(input)
+    fn monkey_map(input: &str) -> IResult<&str, Vec<Vec<Tile>>> {
+        separated_list1(line_ending, many1(tile)).parse(input)
     }
 
     fn instructions(input: &str) -> IResult<&str, Vec<Instruction>> {
@@ -669,7 +813,7 @@ mod parser {
         }
     }
 
-    pub(super) fn parse_input(input: &str) -> IResult<&str, (Map, Vec<Instruction>)> {
+    pub(super) fn parse_input(input: &str) -> IResult<&str, (Vec<Vec<Tile>>, Vec<Instruction>)> {
         terminated(
             separated_pair(monkey_map, tuple((line_ending, line_ending)), instructions),
             tuple((many0(line_ending), eof)),
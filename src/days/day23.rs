@@ -0,0 +1,254 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::geom::{Dir4, Point2, Vec2};
+use crate::solution::{Answer, DayError, Solution, SolveError};
+use crate::spatial::Quadtree;
+
+#[derive(Debug)]
+enum Error {
+    InvalidTile(u8),
+    NoElves,
+}
+
+impl std::error::Error for Error {}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::InvalidTile(b) => write!(f, "invalid tile: {:?}", char::from(*b)),
+            Error::NoElves => f.write_str("no elves in the grove"),
+        }
+    }
+}
+
+impl DayError for Error {
+    fn day(&self) -> u8 {
+        23
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct Elf {
+    id: u64,
+}
+
+const DIRECTIONS: [Dir4; 4] = [Dir4::North, Dir4::South, Dir4::West, Dir4::East];
+
+pub struct Day23;
+
+impl Solution for Day23 {
+    fn day(&self) -> u8 {
+        23
+    }
+
+    fn title(&self) -> &'static str {
+        "Unstable Diffusion"
+    }
+
+    fn parse(&self, input: &str) -> Result<(), SolveError> {
+        Grove::parse(input)?;
+        Ok(())
+    }
+
+    fn part1(&self, input: &str) -> Result<Answer, SolveError> {
+        part1(input)
+    }
+
+    fn part2(&self, input: &str) -> Result<Answer, SolveError> {
+        part2(input)
+    }
+}
+
+pub fn part1(input: &str) -> Result<Answer, SolveError> {
+    let mut grove = Grove::parse(input)?;
+
+    for i in 0..10 {
+        grove.round(i);
+    }
+
+    Ok(grove.empty_ground().into())
+}
+
+// The elves only spread out, so the grove never goes back to an earlier state until it stops
+// changing altogether, which is the answer. There's no cycle for `crate::cycle` to skip round.
+pub fn part2(input: &str) -> Result<Answer, SolveError> {
+    let mut grove = Grove::parse(input)?;
+
+    for i in 0.. {
+        if !grove.round(i) {
+            return Ok((i + 1).into());
+        }
+    }
+
+    unreachable!()
+}
+
+struct Grove {
+    tree: Quadtree<Elf>,
+    elves: HashMap<u64, Point2>,
+}
+
+impl Grove {
+    fn parse(input: &str) -> Result<Grove, Error> {
+        let mut elf_id = 0;
+
+        let bytes = input
+            .lines()
+            .map(|line| line.bytes().collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+
+        let mut elves = HashMap::new();
+
+        for (y, line) in bytes.iter().enumerate() {
+            for (x, &byte) in line.iter().enumerate() {
+                match byte {
+                    b'#' => {
+                        let position = Point2 {
+                            x: x as i64,
+                            y: y as i64,
+                        };
+
+                        elves.insert(elf_id, position);
+                        elf_id += 1;
+                    }
+                    b'.' => {}
+                    _ => return Err(Error::InvalidTile(byte)),
+                }
+            }
+        }
+
+        if elves.is_empty() {
+            return Err(Error::NoElves);
+        }
+
+        let tree = elves.iter().map(|(&id, &pos)| (pos, Elf { id })).collect();
+
+        Ok(Grove { tree, elves })
+    }
+
+    // Run round `i` (counting from 0), returning whether any elf moved.
+    fn round(&mut self, i: usize) -> bool {
+        let directions = DIRECTIONS
+            .iter()
+            .cycle()
+            .skip(i)
+            .take(4)
+            .collect::<Vec<_>>();
+
+        let mut intentions = self
+            .elves
+            .iter()
+            .filter_map(|(&id, &pos)| {
+                let bounds = (pos - Vec2::new(1, 1), pos + Vec2::new(1, 1));
+
+                let neighbours: HashSet<_> = self
+                    .tree
+                    .query(bounds)
+                    .map(|(p, _)| p)
+                    .filter(|p| *p != pos)
+                    .collect::<HashSet<_>>();
+
+                if neighbours.is_empty() {
+                    return None;
+                }
+
+                for dir in &directions {
+                    // The square in that direction, and the ones either side of it.
+                    let b = pos + dir.vec();
+                    let a = b + dir.turn_left().vec();
+                    let c = b + dir.turn_right().vec();
+
+                    if !neighbours.contains(&a)
+                        && !neighbours.contains(&b)
+                        && !neighbours.contains(&c)
+                    {
+                        return Some((id, b));
+                    }
+                }
+
+                None
+            })
+            .collect::<HashMap<_, _>>();
+
+        let intention_count: HashMap<_, _> =
+            intentions
+                .iter()
+                .map(|(_, &pos)| pos)
+                .fold(HashMap::new(), |mut map, pos| {
+                    *map.entry(pos).or_insert(0) += 1;
+                    map
+                });
+
+        intentions.retain(|_, pos| intention_count.get(pos).copied().unwrap_or(0) == 1);
+
+        if intentions.is_empty() {
+            return false;
+        }
+
+        for (&id, &pos) in intentions.iter() {
+            let old_pos = self.elves.insert(id, pos).unwrap();
+            self.tree.remove(old_pos, |e| e.id == id);
+            self.tree.insert(pos, Elf { id });
+        }
+
+        true
+    }
+
+    // Count the empty tiles in the smallest rectangle containing every elf.
+    fn empty_ground(&self) -> i64 {
+        let (min, max) = self.elves.values().fold(
+            (
+                Point2 {
+                    x: i64::MAX,
+                    y: i64::MAX,
+                },
+                Point2 {
+                    x: i64::MIN,
+                    y: i64::MIN,
+                },
+            ),
+            |(min, max), pos| {
+                (
+                    Point2 {
+                        x: min.x.min(pos.x),
+                        y: min.y.min(pos.y),
+                    },
+                    Point2 {
+                        x: max.x.max(pos.x),
+                        y: max.y.max(pos.y),
+                    },
+                )
+            },
+        );
+
+        let width = max.x - min.x + 1;
+        let height = max.y - min.y + 1;
+
+        width * height - self.elves.len() as i64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+        ....#..\n\
+        ..###.#\n\
+        #...#.#\n\
+        .#...##\n\
+        #.###..\n\
+        ##.#.##\n\
+        .#..#..\n\
+    ";
+
+    #[test]
+    fn test_example_part1() {
+        assert_eq!(part1(EXAMPLE).unwrap(), Answer::Number(110));
+    }
+
+    #[test]
+    fn test_example_part2() {
+        assert_eq!(part2(EXAMPLE).unwrap(), Answer::Number(20));
+    }
+}
@@ -0,0 +1,388 @@
+use rayon::prelude::*;
+
+use crate::geom::Dir4;
+use crate::grid::{Grid, Pos};
+use crate::parser::nom_parse_to_owned;
+use crate::search;
+use crate::solution::{Answer, DayError, Solution, SolveError};
+
+#[derive(Debug)]
+enum Error {
+    // Counting trips from 1.
+    NoWayAcross(usize),
+}
+
+impl std::error::Error for Error {}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::NoWayAcross(trip) => write!(f, "no way across the valley on trip {}", trip),
+        }
+    }
+}
+
+impl DayError for Error {
+    fn day(&self) -> u8 {
+        24
+    }
+}
+
+#[repr(u8)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Direction {
+    Up = 1,
+    Down = 2,
+    Left = 4,
+    Right = 8,
+}
+
+#[derive(Debug, Clone)]
+struct Field {
+    blizzards: Grid<u8>,
+    start_col: usize,
+    end_col: usize,
+}
+
+impl Field {
+    fn step(&self) -> Field {
+        let grid = &self.blizzards;
+        let width = grid.width();
+
+        let blizzards = (0..grid.cells().len())
+            .into_par_iter()
+            .map(|i| {
+                let pos = (i % width, i / width);
+                let from = |d: Dir4| grid[grid.wrapping_offset(pos, d.vec())];
+
+                from(Dir4::West) & Direction::Right as u8
+                    | from(Dir4::East) & Direction::Left as u8
+                    | from(Dir4::North) & Direction::Down as u8
+                    | from(Dir4::South) & Direction::Up as u8
+            })
+            .collect();
+
+        Field {
+            blizzards: Grid::from_vec(width, blizzards),
+            ..*self
+        }
+    }
+
+    fn dims(&self) -> (usize, usize) {
+        (self.blizzards.width(), self.blizzards.height())
+    }
+
+    fn is_clear(&self, pos: Pos) -> bool {
+        self.blizzards[pos] == 0
+    }
+}
+
+// Where the expedition is, and how far through the blizzard cycle. `None` is waiting just outside
+// the valley for a gap to step into the start.
+type Node = (Option<Pos>, usize);
+
+struct InfiniteFields {
+    limit: usize,
+    storage: Vec<Field>,
+}
+
+impl InfiniteFields {
+    fn new(initial: Field) -> Self {
+        fn lcm(a: u64, b: u64) -> u64 {
+            (a * b) / gcd(a, b)
+        }
+
+        fn gcd(a: u64, b: u64) -> u64 {
+            if b == 0 {
+                a
+            } else {
+                gcd(b, a % b)
+            }
+        }
+
+        let (width, height) = initial.dims();
+        let limit = lcm(width as u64, height as u64) as usize;
+        let storage = std::iter::successors(Some(initial), |f| Some(f.step()))
+            .take(limit)
+            .collect();
+
+        InfiniteFields { limit, storage }
+    }
+
+    fn get(&self, i: usize) -> &Field {
+        &self.storage[i % self.limit]
+    }
+
+    // Everywhere the expedition could be a minute after `node`.
+    fn moves(&self, (pos, minute): Node, start: Pos) -> Vec<(Node, u64)> {
+        let field = self.get(minute + 1);
+        let next = (minute + 1) % self.limit;
+
+        let candidates: Vec<Option<Pos>> = match pos {
+            // Stepping in from outside the valley is always an option.
+            None => vec![None, Some(start)],
+            Some(pos) => field
+                .blizzards
+                .neighbours4(pos)
+                .chain([pos])
+                .map(Some)
+                .collect(),
+        };
+
+        candidates
+            .into_iter()
+            .filter(|pos| pos.is_none_or(|pos| field.is_clear(pos)))
+            .map(|pos| ((pos, next), 1))
+            .collect()
+    }
+}
+
+pub struct Day24;
+
+impl Solution for Day24 {
+    fn day(&self) -> u8 {
+        24
+    }
+
+    fn title(&self) -> &'static str {
+        "Blizzard Basin"
+    }
+
+    fn parse(&self, input: &str) -> Result<(), SolveError> {
+        nom_parse_to_owned(parser::parse_input, input)?;
+        Ok(())
+    }
+
+    fn part1(&self, input: &str) -> Result<Answer, SolveError> {
+        part1(input)
+    }
+
+    fn part2(&self, input: &str) -> Result<Answer, SolveError> {
+        part2(input)
+    }
+}
+
+pub fn part1(input: &str) -> Result<Answer, SolveError> {
+    Ok(trips(input, 1)?.into())
+}
+
+pub fn part2(input: &str) -> Result<Answer, SolveError> {
+    // There and back again, and there once more for the snacks.
+    Ok(trips(input, 3)?.into())
+}
+
+// Cross the valley `n` times, alternating direction, returning the minute the last trip ends.
+fn trips(input: &str, n: usize) -> Result<u64, SolveError> {
+    let field = nom_parse_to_owned(parser::parse_input, input)?;
+    let (_dim_x, dim_y) = field.dims();
+
+    let mut from = (field.start_col, 0);
+    let mut to = (field.end_col, dim_y - 1);
+
+    let fields = InfiniteFields::new(field);
+    let mut minute = 0;
+
+    for trip in 0..n {
+        let path = search::bfs(
+            &|&node: &Node| fields.moves(node, from),
+            [(None, minute % fields.limit)],
+            |&(pos, _)| pos == Some(to),
+        )
+        .ok_or(Error::NoWayAcross(trip + 1))?;
+
+        // Step out of the valley, ready to head back in the other direction.
+        minute += path.cost as usize + 1;
+        (from, to) = (to, from);
+    }
+
+    Ok(minute as u64)
+}
+
+mod parser {
+    use super::*;
+
+    use nom::{
+        branch::alt,
+        bytes::complete::tag,
+        character::complete::{char, line_ending},
+        combinator::{eof, map, map_opt, value},
+        multi::{many0, many1, many1_count, separated_list1},
+        sequence::{delimited, terminated, tuple},
+        IResult, Parser,
+    };
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    enum Tile {
+        Ground,
+        Blizzard(Direction),
+    }
+
+    impl Tile {
+        fn into_u8(self) -> u8 {
+            match self {
+                Tile::Ground => 0,
+                Tile::Blizzard(direction) => direction as u8,
+            }
+        }
+    }
+
+    fn blizzard_direction(input: &str) -> IResult<&str, Direction> {
+        alt((
+            value(Direction::Up, tag("^")),
+            value(Direction::Down, tag("v")),
+            value(Direction::Left, tag("<")),
+            value(Direction::Right, tag(">")),
+        ))
+        .parse(input)
+    }
+
+    fn tile(input: &str) -> IResult<&str, Tile> {
+        alt((
+            value(Tile::Ground, char('.')),
+            map(blizzard_direction, Tile::Blizzard),
+        ))
+        .parse(input)
+    }
+
+    fn start_end_line(input: &str) -> IResult<&str, usize> {
+        terminated(many1_count(tag("#")), tuple((tag("."), many1(tag("#")))))
+            .map(|i| i - 1)
+            .parse(input)
+    }
+
+    fn grid_line(input: &str) -> IResult<&str, Vec<Tile>> {
+        delimited(tag("#"), many1(tile), tag("#")).parse(input)
+    }
+
+    fn grid_lines(input: &str) -> IResult<&str, Vec<Vec<Tile>>> {
+        separated_list1(line_ending, grid_line).parse(input)
+    }
+
+    pub(super) fn parse_input(input: &str) -> IResult<&str, Field> {
+        map_opt(
+            tuple((
+                terminated(start_end_line, line_ending),
+                terminated(grid_lines, line_ending),
+                terminated(start_end_line, tuple((many0(line_ending), eof))),
+            )),
+            |(start_col, grid, end_col)| {
+                let blizzards = Grid::from_rows(grid)?.map(|&tile| tile.into_u8());
+
+                Some(Field {
+                    blizzards,
+                    start_col,
+                    end_col,
+                })
+            },
+        )
+        .parse(input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_input() {
+        let input = "\
+            #.#####\n\
+            #.....#\n\
+            #>....#\n\
+            #.....#\n\
+            #...v.#\n\
+            #.....#\n\
+            #####.#";
+
+        let (remaining, parsed) = parser::parse_input(input).unwrap();
+        assert_eq!(remaining, "");
+
+        let r = Direction::Right as u8;
+        let d = Direction::Down as u8;
+
+        #[rustfmt::skip]
+        let expected = vec![
+            0,0,0,0,0,
+            r,0,0,0,0,
+            0,0,0,0,0,
+            0,0,0,d,0,
+            0,0,0,0,0,
+        ];
+
+        assert_eq!(parsed.blizzards.cells(), expected);
+        assert_eq!(parsed.dims(), (5, 5));
+        assert_eq!(parsed.start_col, 0);
+        assert_eq!(parsed.end_col, 4);
+
+        let field = parsed.step();
+
+        #[rustfmt::skip]
+        let expected = vec![
+            0,0,0,0,0,
+            0,r,0,0,0,
+            0,0,0,0,0,
+            0,0,0,0,0,
+            0,0,0,d,0,
+        ];
+
+        assert_eq!(field.blizzards.cells(), expected);
+
+        let field = field.step();
+
+        #[rustfmt::skip]
+        let expected = vec![
+            0,0,0,d,0,
+            0,0,r,0,0,
+            0,0,0,0,0,
+            0,0,0,0,0,
+            0,0,0,0,0,
+        ];
+
+        assert_eq!(field.blizzards.cells(), expected);
+
+        let field = field.step();
+
+        #[rustfmt::skip]
+        let expected = vec![
+            0,0,0,0,0,
+            0,0,0,r|d,0,
+            0,0,0,0,0,
+            0,0,0,0,0,
+            0,0,0,0,0,
+        ];
+
+        assert_eq!(field.blizzards.cells(), expected);
+
+        let field = field.step();
+
+        #[rustfmt::skip]
+        let expected = vec![
+            0,0,0,0,0,
+            0,0,0,0,r,
+            0,0,0,d,0,
+            0,0,0,0,0,
+            0,0,0,0,0,
+        ];
+
+        assert_eq!(field.blizzards.cells(), expected);
+    }
+
+    const EXAMPLE: &str = "\
+        #.######\n\
+        #>>.<^<#\n\
+        #.<..<<#\n\
+        #>v.><>#\n\
+        #<^v^^>#\n\
+        ######.#\n\
+    ";
+
+    #[test]
+    fn test_example_part1() {
+        assert_eq!(part1(EXAMPLE).unwrap(), Answer::Number(18));
+    }
+
+    #[test]
+    fn test_example_part2() {
+        assert_eq!(part2(EXAMPLE).unwrap(), Answer::Number(54));
+    }
+}
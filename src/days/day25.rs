@@ -0,0 +1,376 @@
+use std::fmt::Write;
+use std::io::BufRead;
+use std::iter::Sum;
+use std::ops::{Add, Mul, Neg, Sub};
+
+use crate::parser::parse_lines;
+use crate::solution::{Answer, DayError, Part, Solution, SolveError};
+
+// Why a `Snafu` couldn't be read or converted. It's the only way day 25 can fail, so it's the
+// day's error too.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SnafuError {
+    InvalidDigit(u8),
+    NoDigits,
+    TooBig,
+}
+
+impl std::error::Error for SnafuError {}
+
+impl std::fmt::Display for SnafuError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SnafuError::InvalidDigit(b) => write!(f, "unrecognised digit: {:?}", char::from(*b)),
+            SnafuError::NoDigits => f.write_str("number with no digits"),
+            SnafuError::TooBig => f.write_str("number too big to convert"),
+        }
+    }
+}
+
+impl DayError for SnafuError {
+    fn day(&self) -> u8 {
+        25
+    }
+}
+
+// A number in balanced base 5, where the digits run from -2 to 2, so negative numbers don't
+// need a sign. Adding and multiplying work on the digits directly, carrying between them as they
+// go, so a `Snafu` can be as long as it likes.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct Snafu {
+    // Least significant first, with no zeros at the top, so 0 has no digits at all.
+    digits: Vec<i8>,
+}
+
+impl Snafu {
+    pub fn zero() -> Snafu {
+        Snafu::default()
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.digits.is_empty()
+    }
+
+    // Sorts out sums of digits that have gone past -2 or 2, carrying into the next one up.
+    fn carry(sums: impl IntoIterator<Item = i64>) -> Snafu {
+        let mut digits = vec![];
+        let mut carry = 0;
+
+        let mut sums = sums.into_iter();
+        while let Some(sum) = sums
+            .next()
+            .map(|n| n + carry)
+            .or((carry != 0).then_some(carry))
+        {
+            let digit = (sum + 2).rem_euclid(5) - 2;
+            carry = (sum - digit) / 5;
+            digits.push(digit as i8);
+        }
+
+        while digits.last() == Some(&0) {
+            digits.pop();
+        }
+
+        Snafu { digits }
+    }
+}
+
+macro_rules! snafu_integer {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for Snafu {
+                fn from(n: $t) -> Snafu {
+                    // Split into remainders first, which run from -4 to 4 since `%` keeps the
+                    // sign, and let carrying bring them back to -2 to 2.
+                    let mut sums = vec![];
+                    let mut n = n;
+                    while n != 0 {
+                        sums.push((n % 5) as i64);
+                        n /= 5;
+                    }
+
+                    Snafu::carry(sums)
+                }
+            }
+
+            impl TryFrom<&Snafu> for $t {
+                type Error = SnafuError;
+
+                fn try_from(n: &Snafu) -> Result<$t, SnafuError> {
+                    let step = |value: $t, d: $t| value.checked_mul(5)?.checked_add(d);
+
+                    let value = n.digits.iter().rev().try_fold(0 as $t, |value, &d| {
+                        // Right at the ends of the range, a number that fits can overshoot on
+                        // the way, before the last digit brings it back.
+                        let s = value.signum();
+                        step(value, d.into()).or_else(|| step(value - s, <$t>::from(d) + 5 * s))
+                    });
+
+                    value.ok_or(SnafuError::TooBig)
+                }
+            }
+
+            impl TryFrom<Snafu> for $t {
+                type Error = SnafuError;
+
+                fn try_from(n: Snafu) -> Result<$t, SnafuError> {
+                    <$t>::try_from(&n)
+                }
+            }
+        )*
+    };
+}
+
+snafu_integer!(i64, i128);
+
+impl std::str::FromStr for Snafu {
+    type Err = SnafuError;
+
+    fn from_str(s: &str) -> Result<Snafu, SnafuError> {
+        if s.is_empty() {
+            return Err(SnafuError::NoDigits);
+        }
+
+        let digits = s
+            .bytes()
+            .rev()
+            .map(|b| match b {
+                b'0' => Ok(0),
+                b'1' => Ok(1),
+                b'2' => Ok(2),
+                b'-' => Ok(-1),
+                b'=' => Ok(-2),
+                _ => Err(SnafuError::InvalidDigit(b)),
+            })
+            .collect::<Result<Vec<i64>, _>>()?;
+
+        Ok(Snafu::carry(digits))
+    }
+}
+
+impl std::fmt::Display for Snafu {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.is_zero() {
+            return f.write_str("0");
+        }
+
+        for &d in self.digits.iter().rev() {
+            f.write_char(match d {
+                -2 => '=',
+                -1 => '-',
+                0 => '0',
+                1 => '1',
+                2 => '2',
+                _ => unreachable!("digit {} out of range", d),
+            })?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Neg for Snafu {
+    type Output = Snafu;
+
+    fn neg(self) -> Snafu {
+        Snafu {
+            digits: self.digits.into_iter().map(|d| -d).collect(),
+        }
+    }
+}
+
+impl Add for Snafu {
+    type Output = Snafu;
+
+    fn add(self, other: Snafu) -> Snafu {
+        let len = self.digits.len().max(other.digits.len());
+        let digit = |n: &Snafu, i| n.digits.get(i).copied().unwrap_or(0) as i64;
+
+        Snafu::carry((0..len).map(|i| digit(&self, i) + digit(&other, i)))
+    }
+}
+
+impl Sub for Snafu {
+    type Output = Snafu;
+
+    fn sub(self, other: Snafu) -> Snafu {
+        self + -other
+    }
+}
+
+// Long multiplication, a digit at a time.
+impl Mul for Snafu {
+    type Output = Snafu;
+
+    fn mul(self, other: Snafu) -> Snafu {
+        if self.is_zero() || other.is_zero() {
+            return Snafu::zero();
+        }
+
+        let mut sums = vec![0; self.digits.len() + other.digits.len() - 1];
+        for (i, &a) in self.digits.iter().enumerate() {
+            for (j, &b) in other.digits.iter().enumerate() {
+                sums[i + j] += (a * b) as i64;
+            }
+        }
+
+        Snafu::carry(sums)
+    }
+}
+
+impl Sum for Snafu {
+    fn sum<I: Iterator<Item = Snafu>>(iter: I) -> Snafu {
+        iter.fold(Snafu::zero(), Add::add)
+    }
+}
+
+pub struct Day25;
+
+impl Solution for Day25 {
+    fn day(&self) -> u8 {
+        25
+    }
+
+    fn title(&self) -> &'static str {
+        "Full of Hot Air"
+    }
+
+    fn parse(&self, input: &str) -> Result<(), SolveError> {
+        parse_lines(input.as_bytes(), str::parse::<Snafu>).try_for_each(|n| n.map(drop))
+    }
+
+    fn part1(&self, input: &str) -> Result<Answer, SolveError> {
+        part1(input)
+    }
+
+    // Day 25 only has one puzzle, the second star comes free with the other 49.
+    fn part2(&self, _input: &str) -> Result<Answer, SolveError> {
+        Err(SolveError::NoSuchPart {
+            day: 25,
+            part: Part::Two,
+        })
+    }
+
+    fn parts(&self) -> &'static [Part] {
+        &[Part::One]
+    }
+}
+
+pub fn part1(input: &str) -> Result<Answer, SolveError> {
+    part1_from_reader(input.as_bytes())
+}
+
+pub fn part1_from_reader<R: BufRead>(reader: R) -> Result<Answer, SolveError> {
+    let total = parse_lines(reader, str::parse::<Snafu>).sum::<Result<Snafu, _>>()?;
+
+    Ok(total.to_string().into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn snafu(s: &str) -> Snafu {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn test_snafu_convert() {
+        for i in -100_000..100_000i64 {
+            let s = Snafu::from(i).to_string();
+            let n = i64::try_from(snafu(&s)).unwrap();
+
+            assert_eq!(n, i);
+        }
+
+        let expected = [
+            (0, "0"),
+            (1, "1"),
+            (2, "2"),
+            (3, "1="),
+            (4, "1-"),
+            (5, "10"),
+            (6, "11"),
+            (7, "12"),
+            (8, "2="),
+            (9, "2-"),
+            (10, "20"),
+            (15, "1=0"),
+            (20, "1-0"),
+            (2022, "1=11-2"),
+            (12345, "1-0---0"),
+            (314159265, "1121-1110-1=0"),
+            (-1, "-"),
+            (-3, "-2"),
+            (-2022, "-2--1="),
+        ];
+
+        for (n, s) in expected {
+            assert_eq!(Snafu::from(n).to_string(), s);
+            assert_eq!(i64::try_from(snafu(s)).unwrap(), n);
+            assert_eq!(Snafu::from(n as i128), snafu(s));
+        }
+
+        // Leading zeros don't count.
+        assert_eq!(snafu("001-"), snafu("1-"));
+        assert_eq!("".parse::<Snafu>(), Err(SnafuError::NoDigits));
+        assert_eq!("12a".parse::<Snafu>(), Err(SnafuError::InvalidDigit(b'a')));
+
+        assert_eq!(Snafu::from(i64::MAX).to_string().len(), 28);
+        let big = Snafu::from(i64::MAX) + Snafu::from(1i64);
+        assert_eq!(i64::try_from(&big), Err(SnafuError::TooBig));
+        assert_eq!(i128::try_from(&big).unwrap(), i64::MAX as i128 + 1);
+
+        // The very ends of the range overshoot on the way back, before the last digit.
+        for n in [i64::MIN, i64::MIN + 1, i64::MAX - 1, i64::MAX] {
+            assert_eq!(i64::try_from(snafu(&Snafu::from(n).to_string())), Ok(n));
+        }
+        for n in [i128::MIN, i128::MIN + 1, i128::MAX - 1, i128::MAX] {
+            assert_eq!(i128::try_from(snafu(&Snafu::from(n).to_string())), Ok(n));
+        }
+        let small = Snafu::from(i64::MIN) - Snafu::from(1i64);
+        assert_eq!(i64::try_from(&small), Err(SnafuError::TooBig));
+    }
+
+    #[test]
+    fn test_snafu_arithmetic() {
+        for a in -60..60i64 {
+            for b in -60..60i64 {
+                let (x, y) = (Snafu::from(a), Snafu::from(b));
+
+                assert_eq!(x.clone() + y.clone(), Snafu::from(a + b), "{} + {}", a, b);
+                assert_eq!(x.clone() - y.clone(), Snafu::from(a - b), "{} - {}", a, b);
+                assert_eq!(x * y, Snafu::from(a * b), "{} * {}", a, b);
+            }
+        }
+
+        assert_eq!(snafu("2=") - snafu("2="), Snafu::zero());
+        assert_eq!(
+            [snafu("1="), snafu("-"), snafu("2=-1=0")]
+                .into_iter()
+                .sum::<Snafu>(),
+            snafu("2=-1=0") + snafu("2")
+        );
+    }
+
+    const EXAMPLE: &str = "\
+        1=-0-2\n\
+        12111\n\
+        2=0=\n\
+        21\n\
+        2=01\n\
+        111\n\
+        20012\n\
+        112\n\
+        1=-1=\n\
+        1-12\n\
+        12\n\
+        1=\n\
+        122\n\
+    ";
+
+    #[test]
+    fn test_example_part1() {
+        assert_eq!(part1(EXAMPLE).unwrap(), Answer::from("2=-1=0"));
+    }
+}
@@ -0,0 +1,430 @@
+use std::fmt::Display;
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+
+// A point on the plane. The directions below take `y` to grow downwards, like rows in the
+// puzzle inputs; days where it grows upwards can just use `North` for down.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Point2 {
+    pub x: i64,
+    pub y: i64,
+}
+
+// The difference between two points.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Vec2 {
+    pub x: i64,
+    pub y: i64,
+}
+
+// A point in space.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Point3 {
+    pub x: i64,
+    pub y: i64,
+    pub z: i64,
+}
+
+// The difference between two points in space.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Vec3 {
+    pub x: i64,
+    pub y: i64,
+    pub z: i64,
+}
+
+impl Point2 {
+    pub const ORIGIN: Point2 = Point2 { x: 0, y: 0 };
+
+    pub const fn new(x: i64, y: i64) -> Point2 {
+        Point2 { x, y }
+    }
+
+    pub fn manhattan(self, other: Point2) -> i64 {
+        (other - self).manhattan()
+    }
+
+    pub fn chebyshev(self, other: Point2) -> i64 {
+        (other - self).chebyshev()
+    }
+
+    pub fn neighbours4(self) -> impl Iterator<Item = Point2> {
+        Dir4::ALL.into_iter().map(move |d| self + d.vec())
+    }
+
+    pub fn neighbours8(self) -> impl Iterator<Item = Point2> {
+        Dir8::ALL.into_iter().map(move |d| self + d.vec())
+    }
+}
+
+impl Vec2 {
+    pub const fn new(x: i64, y: i64) -> Vec2 {
+        Vec2 { x, y }
+    }
+
+    pub fn manhattan(self) -> i64 {
+        self.x.abs() + self.y.abs()
+    }
+
+    pub fn chebyshev(self) -> i64 {
+        self.x.abs().max(self.y.abs())
+    }
+
+    // Each coordinate clamped to -1, 0 or 1: the step of at most one square towards where this
+    // points.
+    pub fn signum(self) -> Vec2 {
+        Vec2::new(self.x.signum(), self.y.signum())
+    }
+
+    // A quarter turn clockwise, as seen on the screen with `y` growing downwards.
+    pub fn rotate_right(self) -> Vec2 {
+        Vec2::new(-self.y, self.x)
+    }
+
+    pub fn rotate_left(self) -> Vec2 {
+        Vec2::new(self.y, -self.x)
+    }
+}
+
+impl Point3 {
+    pub const fn new(x: i64, y: i64, z: i64) -> Point3 {
+        Point3 { x, y, z }
+    }
+
+    pub fn manhattan(self, other: Point3) -> i64 {
+        (other - self).manhattan()
+    }
+
+    pub fn chebyshev(self, other: Point3) -> i64 {
+        (other - self).chebyshev()
+    }
+
+    // The points sharing a face with this one, if it were a cube.
+    pub fn neighbours6(self) -> impl Iterator<Item = Point3> {
+        Vec3::FACES.into_iter().map(move |d| self + d)
+    }
+}
+
+impl Vec3 {
+    // A step through each face of a cube.
+    pub const FACES: [Vec3; 6] = [
+        Vec3::new(-1, 0, 0),
+        Vec3::new(1, 0, 0),
+        Vec3::new(0, -1, 0),
+        Vec3::new(0, 1, 0),
+        Vec3::new(0, 0, -1),
+        Vec3::new(0, 0, 1),
+    ];
+
+    pub const fn new(x: i64, y: i64, z: i64) -> Vec3 {
+        Vec3 { x, y, z }
+    }
+
+    pub fn manhattan(self) -> i64 {
+        self.x.abs() + self.y.abs() + self.z.abs()
+    }
+
+    pub fn chebyshev(self) -> i64 {
+        self.x.abs().max(self.y.abs()).max(self.z.abs())
+    }
+}
+
+// The four ways along the grid lines.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Dir4 {
+    North,
+    East,
+    South,
+    West,
+}
+
+impl Dir4 {
+    // Clockwise from north.
+    pub const ALL: [Dir4; 4] = [Dir4::North, Dir4::East, Dir4::South, Dir4::West];
+
+    pub fn vec(self) -> Vec2 {
+        match self {
+            Dir4::North => Vec2::new(0, -1),
+            Dir4::East => Vec2::new(1, 0),
+            Dir4::South => Vec2::new(0, 1),
+            Dir4::West => Vec2::new(-1, 0),
+        }
+    }
+
+    pub fn turn_right(self) -> Dir4 {
+        Dir4::ALL[(self as usize + 1) % 4]
+    }
+
+    pub fn turn_left(self) -> Dir4 {
+        Dir4::ALL[(self as usize + 3) % 4]
+    }
+
+    pub fn reverse(self) -> Dir4 {
+        Dir4::ALL[(self as usize + 2) % 4]
+    }
+}
+
+// The grid lines and the diagonals.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Dir8 {
+    North,
+    NorthEast,
+    East,
+    SouthEast,
+    South,
+    SouthWest,
+    West,
+    NorthWest,
+}
+
+impl Dir8 {
+    // Clockwise from north.
+    pub const ALL: [Dir8; 8] = [
+        Dir8::North,
+        Dir8::NorthEast,
+        Dir8::East,
+        Dir8::SouthEast,
+        Dir8::South,
+        Dir8::SouthWest,
+        Dir8::West,
+        Dir8::NorthWest,
+    ];
+
+    pub fn vec(self) -> Vec2 {
+        match self {
+            Dir8::North => Vec2::new(0, -1),
+            Dir8::NorthEast => Vec2::new(1, -1),
+            Dir8::East => Vec2::new(1, 0),
+            Dir8::SouthEast => Vec2::new(1, 1),
+            Dir8::South => Vec2::new(0, 1),
+            Dir8::SouthWest => Vec2::new(-1, 1),
+            Dir8::West => Vec2::new(-1, 0),
+            Dir8::NorthWest => Vec2::new(-1, -1),
+        }
+    }
+
+    // An eighth of a turn clockwise.
+    pub fn turn_right(self) -> Dir8 {
+        Dir8::ALL[(self as usize + 1) % 8]
+    }
+
+    pub fn turn_left(self) -> Dir8 {
+        Dir8::ALL[(self as usize + 7) % 8]
+    }
+
+    pub fn reverse(self) -> Dir8 {
+        Dir8::ALL[(self as usize + 4) % 8]
+    }
+}
+
+impl From<Dir4> for Dir8 {
+    fn from(d: Dir4) -> Dir8 {
+        Dir8::ALL[d as usize * 2]
+    }
+}
+
+impl Add<Vec2> for Point2 {
+    type Output = Point2;
+
+    fn add(self, rhs: Vec2) -> Point2 {
+        Point2::new(self.x + rhs.x, self.y + rhs.y)
+    }
+}
+
+impl AddAssign<Vec2> for Point2 {
+    fn add_assign(&mut self, rhs: Vec2) {
+        *self = *self + rhs;
+    }
+}
+
+impl Sub<Vec2> for Point2 {
+    type Output = Point2;
+
+    fn sub(self, rhs: Vec2) -> Point2 {
+        self + -rhs
+    }
+}
+
+impl SubAssign<Vec2> for Point2 {
+    fn sub_assign(&mut self, rhs: Vec2) {
+        *self = *self - rhs;
+    }
+}
+
+impl Sub<Point2> for Point2 {
+    type Output = Vec2;
+
+    fn sub(self, rhs: Point2) -> Vec2 {
+        Vec2::new(self.x - rhs.x, self.y - rhs.y)
+    }
+}
+
+impl Add for Vec2 {
+    type Output = Vec2;
+
+    fn add(self, rhs: Vec2) -> Vec2 {
+        Vec2::new(self.x + rhs.x, self.y + rhs.y)
+    }
+}
+
+impl Sub for Vec2 {
+    type Output = Vec2;
+
+    fn sub(self, rhs: Vec2) -> Vec2 {
+        self + -rhs
+    }
+}
+
+impl Neg for Vec2 {
+    type Output = Vec2;
+
+    fn neg(self) -> Vec2 {
+        Vec2::new(-self.x, -self.y)
+    }
+}
+
+impl Mul<i64> for Vec2 {
+    type Output = Vec2;
+
+    fn mul(self, rhs: i64) -> Vec2 {
+        Vec2::new(self.x * rhs, self.y * rhs)
+    }
+}
+
+impl Add<Vec3> for Point3 {
+    type Output = Point3;
+
+    fn add(self, rhs: Vec3) -> Point3 {
+        Point3::new(self.x + rhs.x, self.y + rhs.y, self.z + rhs.z)
+    }
+}
+
+impl AddAssign<Vec3> for Point3 {
+    fn add_assign(&mut self, rhs: Vec3) {
+        *self = *self + rhs;
+    }
+}
+
+impl Sub<Vec3> for Point3 {
+    type Output = Point3;
+
+    fn sub(self, rhs: Vec3) -> Point3 {
+        self + -rhs
+    }
+}
+
+impl SubAssign<Vec3> for Point3 {
+    fn sub_assign(&mut self, rhs: Vec3) {
+        *self = *self - rhs;
+    }
+}
+
+impl Sub<Point3> for Point3 {
+    type Output = Vec3;
+
+    fn sub(self, rhs: Point3) -> Vec3 {
+        Vec3::new(self.x - rhs.x, self.y - rhs.y, self.z - rhs.z)
+    }
+}
+
+impl Add for Vec3 {
+    type Output = Vec3;
+
+    fn add(self, rhs: Vec3) -> Vec3 {
+        Vec3::new(self.x + rhs.x, self.y + rhs.y, self.z + rhs.z)
+    }
+}
+
+impl Sub for Vec3 {
+    type Output = Vec3;
+
+    fn sub(self, rhs: Vec3) -> Vec3 {
+        self + -rhs
+    }
+}
+
+impl Neg for Vec3 {
+    type Output = Vec3;
+
+    fn neg(self) -> Vec3 {
+        Vec3::new(-self.x, -self.y, -self.z)
+    }
+}
+
+impl Mul<i64> for Vec3 {
+    type Output = Vec3;
+
+    fn mul(self, rhs: i64) -> Vec3 {
+        Vec3::new(self.x * rhs, self.y * rhs, self.z * rhs)
+    }
+}
+
+// The same as the puzzle inputs write them.
+impl Display for Point2 {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{},{}", self.x, self.y)
+    }
+}
+
+impl Display for Point3 {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{},{},{}", self.x, self.y, self.z)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_arithmetic() {
+        let a = Point2::new(3, -2);
+        let b = Point2::new(-1, 4);
+
+        assert_eq!(b - a, Vec2::new(-4, 6));
+        assert_eq!(a + (b - a), b);
+        assert_eq!(a - Vec2::new(1, 1), Point2::new(2, -3));
+        assert_eq!(-(b - a) * 2, Vec2::new(8, -12));
+        assert_eq!(a.manhattan(b), 10);
+        assert_eq!(a.chebyshev(b), 6);
+        assert_eq!((b - a).signum(), Vec2::new(-1, 1));
+
+        let p = Point3::new(1, 2, 3);
+        let q = Point3::new(-1, -2, -3);
+        assert_eq!(p - Vec3::new(1, 1, 1), Point3::new(0, 1, 2));
+        assert_eq!(q - p, Vec3::new(-2, -4, -6));
+        assert_eq!(p + (q - p), q);
+        assert_eq!(-(q - p) * 2, Vec3::new(4, 8, 12));
+        assert_eq!(p.manhattan(q), 12);
+        assert_eq!(p.chebyshev(Point3::default()), 3);
+        assert_eq!(p.neighbours6().filter(|n| p.manhattan(*n) == 1).count(), 6);
+    }
+
+    #[test]
+    fn test_rotations() {
+        for d in Dir4::ALL {
+            assert_eq!(d.turn_right().vec(), d.vec().rotate_right());
+            assert_eq!(d.turn_left().vec(), d.vec().rotate_left());
+            assert_eq!(d.reverse().vec(), -d.vec());
+            assert_eq!(Dir8::from(d).vec(), d.vec());
+        }
+
+        for d in Dir8::ALL {
+            assert_eq!(d.turn_right().turn_left(), d);
+            assert_eq!(d.reverse().vec(), -d.vec());
+        }
+
+        assert_eq!(Dir4::East.turn_right(), Dir4::South);
+        assert_eq!(Dir8::NorthWest.turn_right(), Dir8::North);
+    }
+
+    #[test]
+    fn test_neighbours() {
+        let p = Point2::new(5, 5);
+
+        assert_eq!(p.neighbours4().count(), 4);
+        assert!(p.neighbours8().all(|n| p.chebyshev(n) == 1));
+        assert!(Point3::default()
+            .neighbours6()
+            .all(|n| n.manhattan(Point3::default()) == 1));
+    }
+}
@@ -0,0 +1,400 @@
+use std::fmt::Display;
+use std::ops::{Index, IndexMut};
+
+use crate::geom::{Dir4, Dir8, Vec2};
+use crate::parser::ParseError;
+
+// A position in a grid, as `(x, y)`: the column, then the row, counted from the top left.
+pub type Pos = (usize, usize);
+
+// A rectangular grid, stored row by row in one `Vec`.
+#[derive(Clone, PartialEq, Eq, Hash, Debug, Default)]
+pub struct Grid<T> {
+    cells: Vec<T>,
+    width: usize,
+    height: usize,
+}
+
+impl<T> Grid<T> {
+    pub fn new(width: usize, height: usize, fill: T) -> Grid<T>
+    where
+        T: Clone,
+    {
+        Grid {
+            cells: vec![fill; width * height],
+            width,
+            height,
+        }
+    }
+
+    pub fn from_fn<F>(width: usize, height: usize, mut f: F) -> Grid<T>
+    where
+        F: FnMut(Pos) -> T,
+    {
+        let cells = (0..height)
+            .flat_map(|y| (0..width).map(move |x| (x, y)))
+            .map(&mut f)
+            .collect();
+
+        Grid {
+            cells,
+            width,
+            height,
+        }
+    }
+
+    // The cells row by row, as `cells()` gives them back.
+    pub fn from_vec(width: usize, cells: Vec<T>) -> Grid<T> {
+        let height = cells.len().checked_div(width).unwrap_or(0);
+        assert_eq!(width * height, cells.len(), "cells don't fill every row");
+
+        Grid {
+            cells,
+            width,
+            height,
+        }
+    }
+
+    // Only if every row is the same length.
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Option<Grid<T>> {
+        let width = rows.first().map_or(0, Vec::len);
+        if rows.iter().any(|row| row.len() != width) {
+            return None;
+        }
+
+        Some(Grid {
+            height: rows.len(),
+            cells: rows.into_iter().flatten().collect(),
+            width,
+        })
+    }
+
+    // Short rows are filled out with `fill` to the length of the longest.
+    pub fn from_rows_padded(rows: Vec<Vec<T>>, fill: T) -> Grid<T>
+    where
+        T: Clone,
+    {
+        let width = rows.iter().map(Vec::len).max().unwrap_or(0);
+        let height = rows.len();
+
+        let cells = rows
+            .into_iter()
+            .flat_map(|mut row| {
+                row.resize(width, fill.clone());
+                row
+            })
+            .collect();
+
+        Grid {
+            cells,
+            width,
+            height,
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, (x, y): Pos) -> bool {
+        x < self.width && y < self.height
+    }
+
+    pub fn get(&self, pos: Pos) -> Option<&T> {
+        self.index_of(pos).map(|i| &self.cells[i])
+    }
+
+    pub fn get_mut(&mut self, pos: Pos) -> Option<&mut T> {
+        self.index_of(pos).map(|i| &mut self.cells[i])
+    }
+
+    fn index_of(&self, pos: Pos) -> Option<usize> {
+        self.contains(pos).then(|| pos.1 * self.width + pos.0)
+    }
+
+    pub fn cells(&self) -> &[T] {
+        &self.cells
+    }
+
+    pub fn positions(&self) -> impl Iterator<Item = Pos> {
+        let width = self.width;
+        (0..self.height).flat_map(move |y| (0..width).map(move |x| (x, y)))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Pos, &T)> + '_ {
+        self.positions().zip(&self.cells)
+    }
+
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (Pos, &mut T)> + '_ {
+        self.positions().zip(&mut self.cells)
+    }
+
+    // Every position holding a cell that matches.
+    pub fn find<'a, F>(&'a self, mut f: F) -> impl Iterator<Item = Pos> + 'a
+    where
+        F: FnMut(&T) -> bool + 'a,
+    {
+        self.iter()
+            .filter_map(move |(pos, cell)| f(cell).then_some(pos))
+    }
+
+    // Moves by `d`, unless that would leave the grid.
+    pub fn offset(&self, (x, y): Pos, d: Vec2) -> Option<Pos> {
+        let pos = (
+            x.checked_add_signed(d.x.try_into().ok()?)?,
+            y.checked_add_signed(d.y.try_into().ok()?)?,
+        );
+        self.contains(pos).then_some(pos)
+    }
+
+    // Moves by `d`, coming back in on the opposite side when leaving the grid. Panics on an empty
+    // grid, which has no other side to come back in on.
+    pub fn wrapping_offset(&self, (x, y): Pos, d: Vec2) -> Pos {
+        debug_assert!(
+            self.width > 0 && self.height > 0,
+            "can't wrap round the empty {}x{} grid",
+            self.width,
+            self.height
+        );
+
+        (
+            (x as i64 + d.x).rem_euclid(self.width as i64) as usize,
+            (y as i64 + d.y).rem_euclid(self.height as i64) as usize,
+        )
+    }
+
+    // Up, right, down and left, leaving out any outside the grid.
+    pub fn neighbours4(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        Dir4::ALL
+            .into_iter()
+            .filter_map(move |d| self.offset(pos, d.vec()))
+    }
+
+    // The same as `neighbours4`, and the diagonals too.
+    pub fn neighbours8(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        Dir8::ALL
+            .into_iter()
+            .filter_map(move |d| self.offset(pos, d.vec()))
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn row_mut(&mut self, y: usize) -> &mut [T] {
+        &mut self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> + '_ {
+        (0..self.height).map(|y| self.row(y))
+    }
+
+    // Top to bottom, and reversible, like a row.
+    pub fn column(&self, x: usize) -> impl DoubleEndedIterator<Item = &T> + ExactSizeIterator {
+        assert!(x < self.width, "column {} out of bounds", x);
+        self.cells.iter().skip(x).step_by(self.width)
+    }
+
+    pub fn column_mut(
+        &mut self,
+        x: usize,
+    ) -> impl DoubleEndedIterator<Item = &mut T> + ExactSizeIterator {
+        assert!(x < self.width, "column {} out of bounds", x);
+        self.cells.iter_mut().skip(x).step_by(self.width)
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl DoubleEndedIterator<Item = &T>> + '_ {
+        (0..self.width).map(|x| self.column(x))
+    }
+
+    // Rows become columns, and columns rows.
+    pub fn transposed(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        Grid::from_fn(self.height, self.width, |(x, y)| self[(y, x)].clone())
+    }
+
+    pub fn map<U, F>(&self, f: F) -> Grid<U>
+    where
+        F: FnMut(&T) -> U,
+    {
+        Grid {
+            cells: self.cells.iter().map(f).collect(),
+            width: self.width,
+            height: self.height,
+        }
+    }
+
+    pub fn try_map<U, E, F>(&self, f: F) -> Result<Grid<U>, E>
+    where
+        F: FnMut(&T) -> Result<U, E>,
+    {
+        Ok(Grid {
+            cells: self.cells.iter().map(f).collect::<Result<_, _>>()?,
+            width: self.width,
+            height: self.height,
+        })
+    }
+
+    // One character per cell, one line per row.
+    pub fn render<F>(&self, mut f: F) -> String
+    where
+        F: FnMut(&T) -> char,
+    {
+        self.rows()
+            .map(|row| row.iter().map(&mut f).collect::<String>())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+impl Grid<u8> {
+    // A character map, one byte per cell. Every line has to be as long as the first.
+    pub fn parse(input: &str) -> Result<Grid<u8>, ParseError> {
+        let mut lines = input.lines().peekable();
+        let width = lines.peek().map_or(0, |line| line.len());
+
+        let mut cells = Vec::with_capacity(input.len());
+        for (y, line) in lines.enumerate() {
+            if line.len() != width {
+                // The width is in bytes, but the column is in characters.
+                let end = width.min(line.len());
+                let column = line.char_indices().take_while(|&(i, _)| i < end).count() + 1;
+
+                return Err(ParseError {
+                    line: y + 1,
+                    column,
+                    snippet: line.to_owned(),
+                    expected: if line.len() < width {
+                        "a row as long as the first"
+                    } else {
+                        "the end of the row"
+                    },
+                });
+            }
+
+            cells.extend_from_slice(line.as_bytes());
+        }
+
+        Ok(Grid::from_vec(width, cells))
+    }
+}
+
+// Panics outside the grid, where `get` would give `None`.
+impl<T> Index<Pos> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: Pos) -> &T {
+        match self.index_of(pos) {
+            Some(i) => &self.cells[i],
+            None => panic!(
+                "{:?} is outside the {}x{} grid",
+                pos, self.width, self.height
+            ),
+        }
+    }
+}
+
+impl<T> IndexMut<Pos> for Grid<T> {
+    fn index_mut(&mut self, pos: Pos) -> &mut T {
+        match self.index_of(pos) {
+            Some(i) => &mut self.cells[i],
+            None => panic!(
+                "{:?} is outside the {}x{} grid",
+                pos, self.width, self.height
+            ),
+        }
+    }
+}
+
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (y, row) in self.rows().enumerate() {
+            if y > 0 {
+                writeln!(f)?;
+            }
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "abc\ndef";
+
+    #[test]
+    fn test_parse() {
+        let grid = Grid::parse(EXAMPLE).unwrap();
+
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid[(1, 0)], b'b');
+        assert_eq!(grid.get((2, 1)), Some(&b'f'));
+        assert_eq!(grid.get((3, 1)), None);
+        assert_eq!(grid.render(|&b| char::from(b)), EXAMPLE);
+    }
+
+    #[test]
+    fn test_parse_uneven() {
+        let e = Grid::parse("abc\nde\nfgh").unwrap_err();
+        assert_eq!((e.line, e.column), (2, 3));
+        assert_eq!(e.snippet, "de");
+
+        let e = Grid::parse("abc\nabcd").unwrap_err();
+        assert_eq!((e.line, e.column), (2, 4));
+
+        // Columns count characters, not bytes.
+        let e = Grid::parse("abcd\né").unwrap_err();
+        assert_eq!((e.line, e.column), (2, 2));
+        let e = Grid::parse("abc\néabc").unwrap_err();
+        assert_eq!((e.line, e.column), (2, 3));
+    }
+
+    #[test]
+    fn test_neighbours() {
+        let grid = Grid::new(3, 3, 0);
+
+        assert_eq!(
+            grid.neighbours4((0, 0)).collect::<Vec<_>>(),
+            vec![(1, 0), (0, 1)]
+        );
+        assert_eq!(grid.neighbours4((1, 1)).count(), 4);
+        assert_eq!(grid.neighbours8((1, 1)).count(), 8);
+        assert_eq!(grid.neighbours8((2, 2)).count(), 3);
+        assert_eq!(grid.wrapping_offset((0, 2), Vec2::new(-1, 1)), (2, 0));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_wrapping_offset_empty() {
+        Grid::new(0, 0, 0).wrapping_offset((0, 0), Vec2::new(1, 0));
+    }
+
+    #[test]
+    fn test_rows_and_columns() {
+        let mut grid = Grid::from_rows(vec![vec![1, 2, 3], vec![4, 5, 6]]).unwrap();
+
+        assert_eq!(grid.row(1), &[4, 5, 6]);
+        assert_eq!(grid.column(2).rev().collect::<Vec<_>>(), vec![&6, &3]);
+        assert_eq!(grid.transposed().to_string(), "14\n25\n36");
+
+        grid.column_mut(0).for_each(|n| *n *= 10);
+        assert_eq!(grid.to_string(), "1023\n4056");
+
+        assert_eq!(Grid::from_rows(vec![vec![1], vec![2, 3]]), None);
+        assert_eq!(
+            Grid::from_rows_padded(vec![vec![1], vec![2, 3]], 0).to_string(),
+            "10\n23"
+        );
+    }
+}
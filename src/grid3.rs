@@ -0,0 +1,265 @@
+use std::ops::{Index, IndexMut};
+
+use crate::geom::Vec3;
+
+// A position in a voxel grid, as `(x, y, z)`.
+pub type Pos3 = (usize, usize, usize);
+
+// A box of voxels, stored in one `Vec` with `x` varying fastest.
+#[derive(Clone, PartialEq, Eq, Hash, Debug, Default)]
+pub struct Grid3<T> {
+    cells: Vec<T>,
+    dims: (usize, usize, usize),
+}
+
+impl<T> Grid3<T> {
+    pub fn new(dims: (usize, usize, usize), fill: T) -> Grid3<T>
+    where
+        T: Clone,
+    {
+        Grid3 {
+            cells: vec![fill; dims.0 * dims.1 * dims.2],
+            dims,
+        }
+    }
+
+    pub fn from_fn<F>(dims: (usize, usize, usize), f: F) -> Grid3<T>
+    where
+        F: FnMut(Pos3) -> T,
+    {
+        Grid3 {
+            cells: positions(dims).map(f).collect(),
+            dims,
+        }
+    }
+
+    pub fn dims(&self) -> (usize, usize, usize) {
+        self.dims
+    }
+
+    pub fn contains(&self, (x, y, z): Pos3) -> bool {
+        x < self.dims.0 && y < self.dims.1 && z < self.dims.2
+    }
+
+    // On one of the six outside faces of the box.
+    pub fn on_boundary(&self, (x, y, z): Pos3) -> bool {
+        let (dx, dy, dz) = self.dims;
+        x == 0 || y == 0 || z == 0 || x + 1 == dx || y + 1 == dy || z + 1 == dz
+    }
+
+    pub fn get(&self, pos: Pos3) -> Option<&T> {
+        self.index_of(pos).map(|i| &self.cells[i])
+    }
+
+    pub fn get_mut(&mut self, pos: Pos3) -> Option<&mut T> {
+        self.index_of(pos).map(|i| &mut self.cells[i])
+    }
+
+    fn index_of(&self, pos: Pos3) -> Option<usize> {
+        let (x, y, z) = pos;
+        let (dx, dy, _) = self.dims;
+        self.contains(pos).then(|| (z * dy + y) * dx + x)
+    }
+
+    pub fn positions(&self) -> impl Iterator<Item = Pos3> {
+        positions(self.dims)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Pos3, &T)> + '_ {
+        self.positions().zip(&self.cells)
+    }
+
+    pub fn map<U, F>(&self, f: F) -> Grid3<U>
+    where
+        F: FnMut(&T) -> U,
+    {
+        Grid3 {
+            cells: self.cells.iter().map(f).collect(),
+            dims: self.dims,
+        }
+    }
+
+    // Moves by `d`, unless that would leave the grid.
+    pub fn offset(&self, (x, y, z): Pos3, d: Vec3) -> Option<Pos3> {
+        let pos = (
+            x.checked_add_signed(d.x.try_into().ok()?)?,
+            y.checked_add_signed(d.y.try_into().ok()?)?,
+            z.checked_add_signed(d.z.try_into().ok()?)?,
+        );
+        self.contains(pos).then_some(pos)
+    }
+
+    // The voxels sharing a face, leaving out any outside the grid.
+    pub fn neighbours6(&self, pos: Pos3) -> impl Iterator<Item = Pos3> + '_ {
+        Vec3::FACES
+            .into_iter()
+            .filter_map(move |d| self.offset(pos, d))
+    }
+
+    // The voxels sharing a face, an edge or a corner.
+    pub fn neighbours26(&self, pos: Pos3) -> impl Iterator<Item = Pos3> + '_ {
+        positions((3, 3, 3))
+            .map(|(x, y, z)| Vec3::new(x as i64 - 1, y as i64 - 1, z as i64 - 1))
+            .filter(|&d| d != Vec3::default())
+            .filter_map(move |d| self.offset(pos, d))
+    }
+
+    // Everything reachable from `starts` through faces, without leaving the voxels that match.
+    // Starts that don't match are left out.
+    pub fn flood_fill<I, F>(&self, starts: I, mut f: F) -> Grid3<bool>
+    where
+        I: IntoIterator<Item = Pos3>,
+        F: FnMut(&T) -> bool,
+    {
+        let mut reached = Grid3::new(self.dims, false);
+        let mut current = vec![];
+
+        for pos in starts {
+            if self.get(pos).is_some_and(&mut f) && !reached[pos] {
+                reached[pos] = true;
+                current.push(pos);
+            }
+        }
+
+        while let Some(pos) = current.pop() {
+            for next in self.neighbours6(pos) {
+                if !reached[next] && f(&self[next]) {
+                    reached[next] = true;
+                    current.push(next);
+                }
+            }
+        }
+
+        reached
+    }
+
+    // Labels each group of matching voxels joined by faces, counting from 0. Also returns how many
+    // groups there are.
+    pub fn components<F>(&self, mut f: F) -> (Grid3<Option<usize>>, usize)
+    where
+        F: FnMut(&T) -> bool,
+    {
+        let mut labels = Grid3::new(self.dims, None);
+        let mut count = 0;
+
+        for start in self.positions() {
+            if labels[start].is_some() || !f(&self[start]) {
+                continue;
+            }
+
+            labels[start] = Some(count);
+            let mut current = vec![start];
+
+            while let Some(pos) = current.pop() {
+                for next in self.neighbours6(pos) {
+                    if labels[next].is_none() && f(&self[next]) {
+                        labels[next] = Some(count);
+                        current.push(next);
+                    }
+                }
+            }
+
+            count += 1;
+        }
+
+        (labels, count)
+    }
+
+    // The number of faces of matching voxels that touch a voxel that doesn't match, or the
+    // outside of the grid.
+    pub fn surface_area<F>(&self, mut f: F) -> usize
+    where
+        F: FnMut(&T) -> bool,
+    {
+        let solid = self.map(&mut f);
+
+        solid
+            .iter()
+            .filter(|(_, &cell)| cell)
+            .map(|(pos, _)| {
+                Vec3::FACES
+                    .into_iter()
+                    .filter(|&d| !solid.offset(pos, d).is_some_and(|next| solid[next]))
+                    .count()
+            })
+            .sum()
+    }
+}
+
+fn positions((dx, dy, dz): (usize, usize, usize)) -> impl Iterator<Item = Pos3> {
+    (0..dz).flat_map(move |z| (0..dy).flat_map(move |y| (0..dx).map(move |x| (x, y, z))))
+}
+
+// Panics outside the grid, where `get` would give `None`.
+impl<T> Index<Pos3> for Grid3<T> {
+    type Output = T;
+
+    fn index(&self, pos: Pos3) -> &T {
+        match self.index_of(pos) {
+            Some(i) => &self.cells[i],
+            None => panic!("{:?} is outside the {:?} grid", pos, self.dims),
+        }
+    }
+}
+
+impl<T> IndexMut<Pos3> for Grid3<T> {
+    fn index_mut(&mut self, pos: Pos3) -> &mut T {
+        match self.index_of(pos) {
+            Some(i) => &mut self.cells[i],
+            None => panic!("{:?} is outside the {:?} grid", pos, self.dims),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // A hollow 3x3x3 cube, with one more voxel off to the side.
+    fn hollow() -> Grid3<bool> {
+        Grid3::from_fn((5, 3, 3), |pos| match pos {
+            (4, 1, 1) => true,
+            (1, 1, 1) => false,
+            (x, _, _) => x < 3,
+        })
+    }
+
+    #[test]
+    fn test_neighbours() {
+        let grid = Grid3::new((3, 3, 3), ());
+
+        assert_eq!(grid.neighbours6((1, 1, 1)).count(), 6);
+        assert_eq!(grid.neighbours6((0, 0, 0)).count(), 3);
+        assert_eq!(grid.neighbours26((1, 1, 1)).count(), 26);
+        assert_eq!(grid.neighbours26((0, 0, 0)).count(), 7);
+        assert!(grid.on_boundary((1, 2, 1)));
+        assert!(!grid.on_boundary((1, 1, 1)));
+    }
+
+    #[test]
+    fn test_flood_fill() {
+        let grid = hollow();
+        let outside = grid.flood_fill([(3, 0, 0), (0, 0, 0)], |&solid| !solid);
+
+        assert!(outside[(3, 1, 1)]);
+        assert!(!outside[(1, 1, 1)]);
+        assert!(!outside[(0, 0, 0)]);
+    }
+
+    #[test]
+    fn test_components() {
+        let (labels, count) = hollow().components(|&solid| solid);
+
+        assert_eq!(count, 2);
+        assert_eq!(labels[(0, 0, 0)], Some(0));
+        assert_eq!(labels[(4, 1, 1)], Some(1));
+        assert_eq!(labels[(3, 1, 1)], None);
+    }
+
+    #[test]
+    fn test_surface_area() {
+        // 54 outside, 6 inside and 6 for the one on its own.
+        assert_eq!(hollow().surface_area(|&solid| solid), 66);
+        assert_eq!(Grid3::new((2, 2, 2), true).surface_area(|&b| b), 24);
+    }
+}
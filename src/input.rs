@@ -0,0 +1,110 @@
+use std::error::Error;
+use std::fmt::Display;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+// Where the puzzle inputs live, relative to the working directory.
+pub const DATA_DIR: &str = "data";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Source {
+    Path(PathBuf),
+    Stdin,
+    // The day's input in `DATA_DIR`.
+    Day(u8),
+}
+
+impl Source {
+    pub fn path(&self) -> Option<PathBuf> {
+        match self {
+            Source::Path(path) => Some(path.clone()),
+            Source::Stdin => None,
+            Source::Day(day) => Some(day_path(DATA_DIR, *day)),
+        }
+    }
+
+    // Reads the whole input, normalized so that every parser sees the same line endings.
+    pub fn load(&self) -> Result<String, InputError> {
+        let raw = match self.path() {
+            Some(path) => std::fs::read_to_string(&path),
+            None => {
+                let mut buffer = String::new();
+                std::io::stdin().read_to_string(&mut buffer).map(|_| buffer)
+            }
+        };
+
+        raw.map(|raw| normalize(&raw)).map_err(|error| InputError {
+            source: self.clone(),
+            error,
+        })
+    }
+}
+
+impl Display for Source {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.path() {
+            Some(path) => write!(f, "{}", path.display()),
+            None => f.write_str("stdin"),
+        }
+    }
+}
+
+pub fn day_path<P: AsRef<Path>>(data_dir: P, day: u8) -> PathBuf {
+    data_dir.as_ref().join(format!("day{:02}.txt", day))
+}
+
+// Unix line endings and no trailing newline, which is how the inputs in `data/` are stored.
+pub fn normalize(input: &str) -> String {
+    input
+        .replace("\r\n", "\n")
+        .trim_end_matches('\n')
+        .to_owned()
+}
+
+#[derive(Debug)]
+pub struct InputError {
+    pub source: Source,
+    pub error: std::io::Error,
+}
+
+impl Error for InputError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(&self.error)
+    }
+}
+
+impl Display for InputError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "unable to read {}: {}", self.source, self.error)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize() {
+        assert_eq!(normalize("1\r\n2\r\n\r\n3\r\n"), "1\n2\n\n3");
+        assert_eq!(normalize("1\n2\n\n"), "1\n2");
+        assert_eq!(normalize("  ..#\n"), "  ..#");
+        assert_eq!(normalize(""), "");
+    }
+
+    #[test]
+    fn test_day_path() {
+        assert_eq!(Source::Day(7).path(), Some(PathBuf::from("data/day07.txt")));
+        assert_eq!(day_path("inputs", 25), PathBuf::from("inputs/day25.txt"));
+        assert_eq!(Source::Stdin.path(), None);
+    }
+
+    #[test]
+    fn test_missing_file() {
+        let e = Source::Path(PathBuf::from("no/such/input.txt"))
+            .load()
+            .unwrap_err();
+        assert!(e
+            .to_string()
+            .starts_with("unable to read no/such/input.txt: "));
+    }
+}
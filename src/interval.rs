@@ -0,0 +1,319 @@
+use std::ops::{Add, RangeInclusive, Sub};
+
+// Whole numbers, where there's nothing between one and the next.
+pub trait Integer: Copy + Ord + Add<Output = Self> + Sub<Output = Self> {
+    const ZERO: Self;
+    const ONE: Self;
+
+    fn checked_add(self, other: Self) -> Option<Self>;
+    fn checked_sub(self, other: Self) -> Option<Self>;
+}
+
+macro_rules! integer {
+    ($($t:ty),*) => {
+        $(
+            impl Integer for $t {
+                const ZERO: $t = 0;
+                const ONE: $t = 1;
+
+                fn checked_add(self, other: $t) -> Option<$t> {
+                    <$t>::checked_add(self, other)
+                }
+
+                fn checked_sub(self, other: $t) -> Option<$t> {
+                    <$t>::checked_sub(self, other)
+                }
+            }
+        )*
+    };
+}
+
+integer!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+
+// A set of whole numbers, kept as the inclusive ranges that make it up. The ranges are sorted,
+// and none of them overlap or touch, so `1..=2` and `3..=4` are kept as `1..=4`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct IntervalSet<T> {
+    ranges: Vec<(T, T)>,
+}
+
+impl<T: Integer> IntervalSet<T> {
+    pub fn new() -> IntervalSet<T> {
+        IntervalSet { ranges: vec![] }
+    }
+
+    // Empty ranges, where the start is after the end, are left out.
+    pub fn insert(&mut self, range: RangeInclusive<T>) {
+        let (mut start, mut end) = range.into_inner();
+        if start > end {
+            return;
+        }
+
+        // Everything from `lo` to `hi` overlaps or touches the new range, so they all merge.
+        let lo = self
+            .ranges
+            .partition_point(|&(_, e)| e < start && e + T::ONE < start);
+        let hi = self
+            .ranges
+            .partition_point(|&(s, _)| s <= end || s - T::ONE <= end);
+
+        if lo < hi {
+            start = start.min(self.ranges[lo].0);
+            end = end.max(self.ranges[hi - 1].1);
+        }
+
+        self.ranges.splice(lo..hi, [(start, end)]);
+    }
+
+    pub fn remove(&mut self, range: RangeInclusive<T>) {
+        let (start, end) = range.into_inner();
+        if start > end {
+            return;
+        }
+
+        let lo = self.ranges.partition_point(|&(_, e)| e < start);
+        let hi = self.ranges.partition_point(|&(s, _)| s <= end);
+        if lo >= hi {
+            return;
+        }
+
+        // Only the ranges at either end can stick out past the one being removed.
+        let (first, _) = self.ranges[lo];
+        let (_, last) = self.ranges[hi - 1];
+        let mut keep = vec![];
+        if first < start {
+            keep.push((first, start - T::ONE));
+        }
+        if last > end {
+            keep.push((end + T::ONE, last));
+        }
+
+        self.ranges.splice(lo..hi, keep);
+    }
+
+    pub fn union(&self, other: &IntervalSet<T>) -> IntervalSet<T> {
+        let mut out = self.clone();
+        out.extend(other.iter());
+        out
+    }
+
+    pub fn subtract(&self, other: &IntervalSet<T>) -> IntervalSet<T> {
+        let mut out = self.clone();
+        for range in other.iter() {
+            out.remove(range);
+        }
+        out
+    }
+
+    pub fn intersection(&self, other: &IntervalSet<T>) -> IntervalSet<T> {
+        let mut ranges = vec![];
+        let (mut a, mut b) = (
+            self.ranges.iter().peekable(),
+            other.ranges.iter().peekable(),
+        );
+
+        while let (Some(&&(s0, e0)), Some(&&(s1, e1))) = (a.peek(), b.peek()) {
+            let (start, end) = (s0.max(s1), e0.min(e1));
+            if start <= end {
+                ranges.push((start, end));
+            }
+
+            // Whichever ends first can't overlap anything else from the other set.
+            if e0 < e1 {
+                a.next();
+            } else {
+                b.next();
+            }
+        }
+
+        IntervalSet { ranges }
+    }
+
+    pub fn contains(&self, n: T) -> bool {
+        self.containing(n).is_some()
+    }
+
+    // All of `range` is in the set.
+    pub fn contains_range(&self, range: &RangeInclusive<T>) -> bool {
+        range.is_empty()
+            || self
+                .containing(*range.start())
+                .is_some_and(|(_, e)| *range.end() <= e)
+    }
+
+    // Some of `range` is in the set.
+    pub fn overlaps(&self, range: &RangeInclusive<T>) -> bool {
+        let i = self.ranges.partition_point(|&(_, e)| e < *range.start());
+        !range.is_empty() && self.ranges.get(i).is_some_and(|&(s, _)| s <= *range.end())
+    }
+
+    fn containing(&self, n: T) -> Option<(T, T)> {
+        let i = self.ranges.partition_point(|&(_, e)| e < n);
+        self.ranges.get(i).copied().filter(|&(s, _)| s <= n)
+    }
+
+    // How many numbers are in the set, or `None` when that's too many to count in `T`, as with
+    // every `u8` there is.
+    pub fn len(&self) -> Option<T> {
+        self.ranges.iter().try_fold(T::ZERO, |n, &(s, e)| {
+            e.checked_sub(s)?.checked_add(T::ONE)?.checked_add(n)
+        })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    // From lowest to highest.
+    pub fn iter(&self) -> impl Iterator<Item = RangeInclusive<T>> + '_ {
+        self.ranges.iter().map(|&(s, e)| s..=e)
+    }
+
+    // The ranges within `bounds` that aren't in the set, from lowest to highest.
+    pub fn gaps(&self, bounds: RangeInclusive<T>) -> impl Iterator<Item = RangeInclusive<T>> + '_ {
+        let (lo, hi) = bounds.into_inner();
+        // The first number that could be in the next gap, until there's nothing left in bounds.
+        let mut from = Some(lo);
+
+        self.ranges
+            .iter()
+            .map(Some)
+            .chain([None])
+            .filter_map(move |range| {
+                let start = from?;
+
+                match range {
+                    Some(&(_, e)) if e < start => None,
+                    Some(&(s, e)) => {
+                        from = (e < hi).then(|| e + T::ONE);
+                        (s > start).then(|| start..=(s - T::ONE).min(hi))
+                    }
+                    None => {
+                        from = None;
+                        Some(start..=hi)
+                    }
+                }
+            })
+            .filter(|gap| !gap.is_empty())
+    }
+}
+
+impl<T: Integer> Default for IntervalSet<T> {
+    fn default() -> IntervalSet<T> {
+        IntervalSet::new()
+    }
+}
+
+impl<T: Integer> From<RangeInclusive<T>> for IntervalSet<T> {
+    fn from(range: RangeInclusive<T>) -> IntervalSet<T> {
+        let mut out = IntervalSet::new();
+        out.insert(range);
+        out
+    }
+}
+
+impl<T: Integer> Extend<RangeInclusive<T>> for IntervalSet<T> {
+    fn extend<I: IntoIterator<Item = RangeInclusive<T>>>(&mut self, iter: I) {
+        for range in iter {
+            self.insert(range);
+        }
+    }
+}
+
+impl<T: Integer> FromIterator<RangeInclusive<T>> for IntervalSet<T> {
+    fn from_iter<I: IntoIterator<Item = RangeInclusive<T>>>(iter: I) -> IntervalSet<T> {
+        let mut out = IntervalSet::new();
+        out.extend(iter);
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn set(ranges: &[RangeInclusive<i64>]) -> IntervalSet<i64> {
+        ranges.iter().cloned().collect()
+    }
+
+    #[test]
+    fn test_insert() {
+        let mut s = set(&[1..=2, 10..=12, 5..=6]);
+        assert_eq!(s.iter().collect::<Vec<_>>(), vec![1..=2, 5..=6, 10..=12]);
+
+        // Touching ranges join up, as there's nothing between them.
+        s.insert(3..=4);
+        assert_eq!(s.iter().collect::<Vec<_>>(), vec![1..=6, 10..=12]);
+
+        s.insert(0..=11);
+        assert_eq!(s.iter().collect::<Vec<_>>(), vec![0..=12]);
+
+        s.insert(RangeInclusive::new(5, 4));
+        assert_eq!(s.len(), Some(13));
+        assert!(IntervalSet::<u8>::new().is_empty());
+        assert_eq!(IntervalSet::<u8>::new().len(), Some(0));
+
+        // Too many to count in the type itself.
+        assert_eq!(IntervalSet::from(0..=254u8).len(), Some(255));
+        assert_eq!(IntervalSet::from(0..=255u8).len(), None);
+        assert_eq!(IntervalSet::from(-128..=127i8).len(), None);
+        let mut s = IntervalSet::from(-128..=0i8);
+        s.insert(2..=127);
+        assert_eq!(s.len(), None);
+        s.remove(-128..=-1);
+        assert_eq!(s.len(), Some(i8::MAX));
+    }
+
+    #[test]
+    fn test_remove() {
+        let mut s = set(&[0..=10, 20..=30]);
+
+        s.remove(5..=24);
+        assert_eq!(s.iter().collect::<Vec<_>>(), vec![0..=4, 25..=30]);
+
+        s.remove(2..=2);
+        assert_eq!(s.iter().collect::<Vec<_>>(), vec![0..=1, 3..=4, 25..=30]);
+
+        s.remove(-5..=1);
+        s.remove(26..=100);
+        assert_eq!(s.iter().collect::<Vec<_>>(), vec![3..=4, 25..=25]);
+
+        assert_eq!(
+            set(&[0..=10]).subtract(&set(&[1..=1, 3..=3])),
+            set(&[0..=0, 2..=2, 4..=10])
+        );
+    }
+
+    #[test]
+    fn test_intersection_and_union() {
+        let a = set(&[0..=5, 10..=15, 20..=25]);
+        let b = set(&[3..=12, 14..=21]);
+
+        assert_eq!(a.intersection(&b), set(&[3..=5, 10..=12, 14..=15, 20..=21]));
+        assert_eq!(a.union(&b), set(&[0..=25]));
+        assert!(a.intersection(&set(&[6..=9])).is_empty());
+    }
+
+    #[test]
+    fn test_queries() {
+        let s = set(&[-3..=0, 4..=8]);
+
+        assert!(s.contains(-3) && s.contains(6) && !s.contains(2));
+        assert!(s.contains_range(&(5..=8)));
+        assert!(!s.contains_range(&(0..=4)));
+        assert!(s.overlaps(&(0..=4)));
+        assert!(!s.overlaps(&(1..=3)));
+        assert_eq!(s.len(), Some(9));
+
+        assert_eq!(
+            s.gaps(-5..=10).collect::<Vec<_>>(),
+            vec![-5..=-4, 1..=3, 9..=10]
+        );
+        assert_eq!(s.gaps(5..=6).count(), 0);
+        assert_eq!(s.gaps(2..=5).collect::<Vec<_>>(), vec![2..=3]);
+        assert_eq!(
+            IntervalSet::<u8>::new().gaps(1..=2).collect::<Vec<_>>(),
+            vec![1..=2]
+        );
+    }
+}
@@ -1,4 +1,18 @@
+pub mod bench;
+pub mod collections;
+pub mod cycle;
+pub mod days;
+pub mod geom;
+pub mod grid;
+pub mod grid3;
+pub mod input;
+pub mod interval;
 pub mod parser;
+pub mod search;
+pub mod solution;
+pub mod spatial;
+
+pub use solution::{Answer, DayError, Part, Solution, SolveError};
 
 pub fn nothing() {}
 
@@ -1,13 +1,19 @@
-use std::io::Read;
+use std::error::Error;
+use std::fmt::Display;
+use std::io::BufRead;
 use std::iter::Sum;
 use std::str::FromStr;
 
 use nom::bytes::complete::tag;
 use nom::character::complete::digit1;
 use nom::combinator::{map_res, opt, recognize};
+use nom::error::ErrorKind;
 use nom::sequence::preceded;
 use nom::{Finish, IResult, Parser};
 
+use crate::input::Source;
+use crate::solution::SolveError;
+
 pub fn base10_numeric<N>(input: &str) -> IResult<&str, N>
 where
     N: Sum<N> + FromStr,
@@ -18,44 +24,279 @@ where
     .parse(input)
 }
 
-pub fn nom_error_to_owned<I>(e: nom::error::Error<&I>) -> nom::error::Error<I::Owned>
-where
-    I: ToOwned + ?Sized,
-    I::Owned: 'static,
-{
-    let nom::error::Error { input, code } = e;
-    nom::error::Error {
-        input: input.to_owned(),
-        code,
+// Where parsing stopped, and what the parser wanted to see there. Unlike the nom error, which
+// holds on to the rest of the input, this only keeps the line it went wrong on.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    // Both counted from 1, the column in characters.
+    pub line: usize,
+    pub column: usize,
+    pub snippet: String,
+    pub expected: &'static str,
+}
+
+impl ParseError {
+    // `remaining` is the part of `input` the parser gave up on.
+    pub fn new(input: &str, remaining: &str, kind: ErrorKind) -> ParseError {
+        let offset = input.len() - remaining.len();
+        let before = &input[..offset];
+
+        let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+        let line_end = input[offset..]
+            .find('\n')
+            .map_or(input.len(), |i| offset + i);
+
+        ParseError {
+            line: before.matches('\n').count() + 1,
+            column: before[line_start..].chars().count() + 1,
+            snippet: input[line_start..line_end]
+                .trim_end_matches('\r')
+                .to_owned(),
+            expected: expected(kind),
+        }
+    }
+
+    pub fn from_nom(input: &str, e: nom::Err<nom::error::Error<&str>>) -> ParseError {
+        match e {
+            nom::Err::Error(e) | nom::Err::Failure(e) => ParseError::new(input, e.input, e.code),
+            nom::Err::Incomplete(_) => ParseError::new(input, "", ErrorKind::Complete),
+        }
     }
 }
 
-// Lifetime hacks to make the `?` operator usable with nom results.
-//
-// This was more work than just pattern matching it.
-pub fn nom_parse_to_owned<I, O, P>(
-    mut parser: P,
-    input: &I,
-) -> Result<O, nom::error::Error<I::Owned>>
+impl Error for ParseError {}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(
+            f,
+            "line {}, column {}: expected {}",
+            self.line, self.column, self.expected
+        )?;
+        writeln!(f, "    {}", self.snippet)?;
+        write!(f, "    {:>width$}", "^", width = self.column)
+    }
+}
+
+// nom only tells us which combinator failed, which is about as close as it gets to knowing what
+// was supposed to be there.
+fn expected(kind: ErrorKind) -> &'static str {
+    match kind {
+        ErrorKind::Tag => "a fixed piece of text",
+        ErrorKind::Char => "a particular character",
+        ErrorKind::OneOf => "one of a set of characters",
+        ErrorKind::NoneOf => "a character outside a set",
+        ErrorKind::Digit => "a number",
+        ErrorKind::MapRes | ErrorKind::MapOpt => "a value in range",
+        ErrorKind::Alpha => "letters",
+        ErrorKind::AlphaNumeric => "letters or digits",
+        ErrorKind::Space | ErrorKind::MultiSpace => "whitespace",
+        ErrorKind::CrLf => "a line ending",
+        ErrorKind::Eof => "the end of the input",
+        ErrorKind::Complete => "more input",
+        ErrorKind::Alt => "one of several alternatives",
+        ErrorKind::Many1 | ErrorKind::SeparatedList | ErrorKind::ManyTill => "a list of items",
+        ErrorKind::Count | ErrorKind::ManyMN => "a fixed number of items",
+        ErrorKind::TakeWhile1 | ErrorKind::TakeWhileMN | ErrorKind::TakeTill1 => {
+            "a run of matching characters"
+        }
+        ErrorKind::Verify => "a valid value",
+        _ => "valid input",
+    }
+}
+
+// Makes the `?` operator usable with nom results, as long as the output doesn't borrow from the
+// input.
+pub fn nom_parse_to_owned<O, P>(mut parser: P, input: &str) -> Result<O, ParseError>
 where
-    I: ToOwned + ?Sized,
-    I::Owned: 'static,
-    P: for<'i> Parser<&'i I, O, nom::error::Error<&'i I>>,
+    P: for<'i> Parser<&'i str, O, nom::error::Error<&'i str>>,
 {
     match parser.parse(input).finish() {
         Ok((_i, o)) => Ok(o),
-        Err(e) => Err(nom_error_to_owned(e)),
+        Err(e) => Err(ParseError::new(input, e.input, e.code)),
+    }
+}
+
+// For line-oriented inputs: parses each line as it's read, so an input never has to fit in
+// memory. `parse` sees the line without its line ending, and parse errors report where the line
+// is in the whole input.
+pub fn parse_lines<R, F, O, E>(reader: R, parse: F) -> Lines<R, F>
+where
+    R: BufRead,
+    F: FnMut(&str) -> Result<O, E>,
+    E: Into<SolveError>,
+{
+    Lines {
+        reader,
+        parse,
+        buffer: String::new(),
+        line: 0,
+    }
+}
+
+pub struct Lines<R, F> {
+    reader: R,
+    parse: F,
+    // Reused for every line, so reading doesn't allocate once the lines stop getting longer.
+    buffer: String,
+    line: usize,
+}
+
+impl<R, F, O, E> Iterator for Lines<R, F>
+where
+    R: BufRead,
+    F: FnMut(&str) -> Result<O, E>,
+    E: Into<SolveError>,
+{
+    type Item = Result<O, SolveError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.buffer.clear();
+
+        self.line += 1;
+        let number = self.line;
+        let at_line = |error| SolveError::Line {
+            line: number,
+            error: Box::new(error),
+        };
+
+        match self.reader.read_line(&mut self.buffer) {
+            Ok(0) => None,
+            Ok(_) => {
+                let line = self.buffer.strip_suffix('\n').unwrap_or(&self.buffer);
+                let line = line.strip_suffix('\r').unwrap_or(line);
+
+                Some((self.parse)(line).map_err(|e| match e.into() {
+                    SolveError::Parse(e) => SolveError::Parse(ParseError { line: number, ..e }),
+                    e => at_line(e),
+                }))
+            }
+            Err(e) => Some(Err(at_line(SolveError::Other(Box::new(e))))),
+        }
+    }
+}
+
+// Turns a nom parser into a line parser for `parse_lines`. It has to match the whole line.
+pub fn nom_line<O, P>(mut parser: P) -> impl FnMut(&str) -> Result<O, ParseError>
+where
+    P: for<'i> Parser<&'i str, O, nom::error::Error<&'i str>>,
+{
+    move |line: &str| match parser.parse(line).finish() {
+        Ok(("", o)) => Ok(o),
+        Ok((rest, _)) => Err(ParseError::new(line, rest, ErrorKind::Eof)),
+        Err(e) => Err(ParseError::new(line, e.input, e.code)),
     }
 }
 
 // So much parsing. Time for another shortcut.
-pub fn read_from_stdin_and_parse<O, P>(parser: P) -> Result<O, Box<dyn std::error::Error>>
+pub fn read_and_parse<O, P>(source: &Source, parser: P) -> Result<O, Box<dyn std::error::Error>>
 where
     P: for<'i> Parser<&'i str, O, nom::error::Error<&'i str>>,
 {
-    let mut buffer = String::new();
-    std::io::stdin().read_to_string(&mut buffer)?;
+    let input = source.load()?;
 
-    let out = nom_parse_to_owned(parser, &buffer)?;
+    let out = nom_parse_to_owned(parser, &input)?;
     Ok(out)
 }
+
+pub fn read_from_stdin_and_parse<O, P>(parser: P) -> Result<O, Box<dyn std::error::Error>>
+where
+    P: for<'i> Parser<&'i str, O, nom::error::Error<&'i str>>,
+{
+    read_and_parse(&Source::Stdin, parser)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use nom::character::complete::line_ending;
+    use nom::combinator::eof;
+    use nom::multi::separated_list1;
+    use nom::sequence::terminated;
+
+    fn numbers(input: &str) -> IResult<&str, Vec<i64>> {
+        terminated(separated_list1(line_ending, base10_numeric), eof).parse(input)
+    }
+
+    #[test]
+    fn test_parse_ok() {
+        assert_eq!(nom_parse_to_owned(numbers, "1\n-2\n3"), Ok(vec![1, -2, 3]));
+    }
+
+    #[test]
+    fn test_parse_error_position() {
+        let e = nom_parse_to_owned(numbers, "1\n2\n3x\n4\n").unwrap_err();
+
+        assert_eq!(e.line, 3);
+        assert_eq!(e.column, 2);
+        assert_eq!(e.snippet, "3x");
+        assert_eq!(e.expected, "the end of the input");
+        assert_eq!(
+            e.to_string(),
+            "line 3, column 2: expected the end of the input\n    3x\n     ^"
+        );
+    }
+
+    #[test]
+    fn test_parse_error_first_line() {
+        let e = nom_parse_to_owned(numbers, "x1\r\n2").unwrap_err();
+
+        assert_eq!((e.line, e.column), (1, 1));
+        assert_eq!(e.snippet, "x1");
+        assert_eq!(e.expected, "a number");
+    }
+
+    #[test]
+    fn test_parse_lines() {
+        let numbers = parse_lines("1\r\n-2\n\n3".as_bytes(), |line: &str| {
+            Ok::<_, SolveError>(line.parse::<i64>().ok())
+        });
+
+        assert_eq!(
+            numbers.collect::<Result<Vec<_>, _>>().unwrap(),
+            vec![Some(1), Some(-2), None, Some(3)]
+        );
+    }
+
+    #[test]
+    fn test_parse_lines_error_position() {
+        let mut numbers = parse_lines("1\n2\n3x\n4".as_bytes(), nom_line(base10_numeric::<i64>));
+
+        assert_eq!(numbers.next().unwrap().unwrap(), 1);
+        assert_eq!(numbers.next().unwrap().unwrap(), 2);
+
+        let Err(SolveError::Parse(e)) = numbers.next().unwrap() else {
+            panic!("expected a parse error");
+        };
+        assert_eq!((e.line, e.column), (3, 2));
+        assert_eq!(e.snippet, "3x");
+        assert_eq!(e.expected, "the end of the input");
+    }
+
+    #[test]
+    fn test_parse_lines_invalid_utf8() {
+        let mut lines = parse_lines(&b"1\n\xff\n"[..], nom_line(base10_numeric::<i64>));
+
+        assert_eq!(lines.next().unwrap().unwrap(), 1);
+        let Some(Err(SolveError::Line { line: 2, error })) = lines.next() else {
+            panic!("expected an error on line 2");
+        };
+        assert!(matches!(*error, SolveError::Other(_)));
+    }
+
+    #[test]
+    fn test_parse_lines_other_error_position() {
+        let mut numbers = parse_lines("1\n-2\n3".as_bytes(), |line: &str| {
+            match line.parse::<i64>() {
+                Ok(n) if n < 0 => Err(SolveError::from("negative")),
+                n => Ok(n.ok()),
+            }
+        });
+
+        assert_eq!(numbers.next().unwrap().unwrap(), Some(1));
+        let e = numbers.next().unwrap().unwrap_err();
+        assert_eq!(e.to_string(), "line 2: negative");
+        assert_eq!(numbers.next().unwrap().unwrap(), Some(3));
+    }
+}
@@ -0,0 +1,286 @@
+use std::cmp::Reverse;
+use std::collections::hash_map::Entry;
+use std::collections::{BinaryHeap, HashMap, VecDeque};
+use std::hash::Hash;
+
+// Anything that can say where you can get to from a node, and what each step costs. Closures
+// from a node to its neighbours are graphs too.
+pub trait Graph<N> {
+    fn neighbours(&self, node: &N) -> impl Iterator<Item = (N, u64)>;
+}
+
+impl<N, F, I> Graph<N> for F
+where
+    F: Fn(&N) -> I,
+    I: IntoIterator<Item = (N, u64)>,
+{
+    fn neighbours(&self, node: &N) -> impl Iterator<Item = (N, u64)> {
+        self(node).into_iter()
+    }
+}
+
+// The way to a goal, from whichever start it set out from to the goal itself.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Path<N> {
+    pub cost: u64,
+    pub nodes: Vec<N>,
+}
+
+impl<N> Path<N> {
+    pub fn goal(&self) -> &N {
+        self.nodes.last().expect("paths are never empty")
+    }
+}
+
+// Every node explored so far, and the one it was first reached from.
+struct Visited<N> {
+    nodes: Vec<N>,
+    parents: Vec<Option<usize>>,
+    index: HashMap<N, usize>,
+}
+
+impl<N: Clone + Eq + Hash> Visited<N> {
+    fn new() -> Visited<N> {
+        Visited {
+            nodes: vec![],
+            parents: vec![],
+            index: HashMap::new(),
+        }
+    }
+
+    // The node's index, and whether it's the first time it was seen.
+    fn insert(&mut self, node: N, parent: Option<usize>) -> (usize, bool) {
+        match self.index.entry(node) {
+            Entry::Occupied(e) => (*e.get(), false),
+            Entry::Vacant(e) => {
+                let i = self.nodes.len();
+                self.nodes.push(e.key().clone());
+                self.parents.push(parent);
+                e.insert(i);
+                (i, true)
+            }
+        }
+    }
+
+    fn path(&self, cost: u64, mut i: usize) -> Path<N> {
+        let mut nodes = vec![self.nodes[i].clone()];
+        while let Some(parent) = self.parents[i] {
+            nodes.push(self.nodes[parent].clone());
+            i = parent;
+        }
+        nodes.reverse();
+
+        Path { cost, nodes }
+    }
+}
+
+// Breadth-first search, for when every step costs the same: the path found has the fewest steps,
+// and its cost is the number of steps. The step costs from the graph are ignored.
+pub fn bfs<N, G, S, F>(graph: &G, starts: S, mut goal: F) -> Option<Path<N>>
+where
+    N: Clone + Eq + Hash,
+    G: Graph<N>,
+    S: IntoIterator<Item = N>,
+    F: FnMut(&N) -> bool,
+{
+    let mut visited = Visited::new();
+    let mut queue = VecDeque::new();
+
+    for start in starts {
+        if let (i, true) = visited.insert(start, None) {
+            queue.push_back((i, 0));
+        }
+    }
+
+    while let Some((i, steps)) = queue.pop_front() {
+        if goal(&visited.nodes[i]) {
+            return Some(visited.path(steps, i));
+        }
+
+        let node = visited.nodes[i].clone();
+        for (next, _) in graph.neighbours(&node) {
+            if let (j, true) = visited.insert(next, Some(i)) {
+                queue.push_back((j, steps + 1));
+            }
+        }
+    }
+
+    None
+}
+
+// The number of steps to every node that can be reached from `start`, including `start` itself.
+pub fn distances<N, G>(graph: &G, start: N) -> HashMap<N, u64>
+where
+    N: Clone + Eq + Hash,
+    G: Graph<N>,
+{
+    let mut out = HashMap::from([(start.clone(), 0)]);
+    let mut queue = VecDeque::from([(start, 0)]);
+
+    while let Some((node, steps)) = queue.pop_front() {
+        for (next, _) in graph.neighbours(&node) {
+            if let Entry::Vacant(e) = out.entry(next.clone()) {
+                e.insert(steps + 1);
+                queue.push_back((next, steps + 1));
+            }
+        }
+    }
+
+    out
+}
+
+// The cheapest path to a goal.
+pub fn dijkstra<N, G, S, F>(graph: &G, starts: S, goal: F) -> Option<Path<N>>
+where
+    N: Clone + Eq + Hash,
+    G: Graph<N>,
+    S: IntoIterator<Item = N>,
+    F: FnMut(&N) -> bool,
+{
+    astar(graph, starts, goal, |_| 0)
+}
+
+// The cheapest path to a goal, trying the most promising nodes first. To be sure of finding the
+// cheapest, `heuristic` can't ever guess more than it really costs to get to a goal.
+pub fn astar<N, G, S, F, H>(graph: &G, starts: S, mut goal: F, mut heuristic: H) -> Option<Path<N>>
+where
+    N: Clone + Eq + Hash,
+    G: Graph<N>,
+    S: IntoIterator<Item = N>,
+    F: FnMut(&N) -> bool,
+    H: FnMut(&N) -> u64,
+{
+    let mut visited = Visited::new();
+    let mut costs = vec![];
+    let mut heap = BinaryHeap::new();
+
+    for start in starts {
+        if let (i, true) = visited.insert(start, None) {
+            costs.push(0);
+            heap.push(Reverse((heuristic(&visited.nodes[i]), 0, i)));
+        }
+    }
+
+    while let Some(Reverse((_, cost, i))) = heap.pop() {
+        // Already reached some cheaper way since this was queued.
+        if cost > costs[i] {
+            continue;
+        }
+
+        if goal(&visited.nodes[i]) {
+            return Some(visited.path(cost, i));
+        }
+
+        let node = visited.nodes[i].clone();
+        for (next, step) in graph.neighbours(&node) {
+            let next_cost = cost + step;
+            let (j, new) = visited.insert(next, Some(i));
+
+            if new {
+                costs.push(next_cost);
+            } else if next_cost < costs[j] {
+                costs[j] = next_cost;
+                visited.parents[j] = Some(i);
+            } else {
+                continue;
+            }
+
+            heap.push(Reverse((
+                next_cost + heuristic(&visited.nodes[j]),
+                next_cost,
+                j,
+            )));
+        }
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::geom::Point2;
+    use crate::grid::{Grid, Pos};
+
+    // A maze where `#` is a wall, and `~` is water that takes 5 to wade through.
+    const MAZE: &str = "\
+        .~~..\n\
+        .###.\n\
+        .....\n\
+        ####.\n\
+        .#...\n\
+    ";
+
+    fn maze() -> impl Fn(&Pos) -> Vec<(Pos, u64)> {
+        let grid = Grid::parse(MAZE).unwrap();
+
+        move |&pos| {
+            grid.neighbours4(pos)
+                .filter_map(|next| match grid[next] {
+                    b'.' => Some((next, 1)),
+                    b'~' => Some((next, 5)),
+                    _ => None,
+                })
+                .collect()
+        }
+    }
+
+    #[test]
+    fn test_bfs() {
+        let path = bfs(&maze(), [(0, 0)], |&pos| pos == (4, 0)).unwrap();
+
+        assert_eq!(path.cost, 4);
+        assert_eq!(path.nodes, vec![(0, 0), (1, 0), (2, 0), (3, 0), (4, 0)]);
+
+        // From whichever start is closest.
+        let path = bfs(&maze(), [(0, 0), (4, 4)], |&pos| pos == (4, 2)).unwrap();
+        assert_eq!(path.cost, 2);
+        assert_eq!(path.nodes[0], (4, 4));
+        assert_eq!(*path.goal(), (4, 2));
+
+        assert_eq!(bfs(&maze(), [(0, 0)], |&pos| pos == (0, 4)), None);
+    }
+
+    #[test]
+    fn test_dijkstra() {
+        // Around the walls is longer, but cheaper than through the water.
+        let path = dijkstra(&maze(), [(0, 0)], |&pos| pos == (4, 0)).unwrap();
+
+        assert_eq!(path.cost, 8);
+        assert_eq!(path.nodes.len(), 9);
+        assert!(path.nodes.contains(&(2, 2)));
+
+        let path = dijkstra(&maze(), [(0, 0)], |&pos| pos == (2, 0)).unwrap();
+        assert_eq!(path.cost, 10);
+
+        assert_eq!(dijkstra(&maze(), [(0, 0)], |&pos| pos == (0, 4)), None);
+    }
+
+    #[test]
+    fn test_astar_matches_dijkstra() {
+        let graph = maze();
+        let goal = (4, 0);
+        let manhattan = |&(x, y): &Pos| {
+            let a = Point2::new(x as i64, y as i64);
+            a.manhattan(Point2::new(goal.0 as i64, goal.1 as i64)) as u64
+        };
+
+        for start in [(0, 0), (2, 4), (0, 4)] {
+            assert_eq!(
+                astar(&graph, [start], |&pos| pos == goal, manhattan).map(|p| p.cost),
+                dijkstra(&graph, [start], |&pos| pos == goal).map(|p| p.cost),
+            );
+        }
+    }
+
+    #[test]
+    fn test_distances() {
+        let distances = distances(&maze(), (0, 0));
+
+        assert_eq!(distances[&(0, 0)], 0);
+        assert_eq!(distances[&(4, 2)], 6);
+        assert_eq!(distances[&(2, 4)], 10);
+        assert_eq!(distances.get(&(0, 4)), None);
+    }
+}
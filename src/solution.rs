@@ -0,0 +1,190 @@
+use std::error::Error;
+use std::fmt::Display;
+
+use crate::parser::ParseError;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    pub const ALL: [Part; 2] = [Part::One, Part::Two];
+}
+
+impl Display for Part {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Part::One => f.write_str("1"),
+            Part::Two => f.write_str("2"),
+        }
+    }
+}
+
+impl std::str::FromStr for Part {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Part, String> {
+        match s {
+            "1" => Ok(Part::One),
+            "2" => Ok(Part::Two),
+            _ => Err(format!("invalid part: {:?}", s)),
+        }
+    }
+}
+
+// Most answers are numbers, but a few are text (day 5's crates, day 10's picture, day 25's SNAFU
+// number). Numbers are kept as numbers so they can be compared without caring how they're printed.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Answer {
+    Number(i128),
+    Text(String),
+}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Answer::Number(n) => write!(f, "{}", n),
+            Answer::Text(s) => f.write_str(s),
+        }
+    }
+}
+
+macro_rules! answer_from_number {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(n: $t) -> Answer {
+                    Answer::Number(n as i128)
+                }
+            }
+        )*
+    };
+}
+
+answer_from_number!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize, i128);
+
+impl From<String> for Answer {
+    fn from(s: String) -> Answer {
+        Answer::Text(s)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(s: &str) -> Answer {
+        Answer::Text(s.to_owned())
+    }
+}
+
+#[derive(Debug)]
+pub enum SolveError {
+    // The input doesn't look like the puzzle input for the day.
+    Parse(ParseError),
+    // The input parsed fine, but there's no answer to be found in it.
+    Unsolvable(String),
+    // The part doesn't exist (only day 25 part 2).
+    NoSuchPart { day: u8, part: Part },
+    // One of the day's own errors, usually the input breaking an assumption the solution makes.
+    Day(Box<dyn DayError>),
+    Other(Box<dyn Error + Send + Sync>),
+    // Any other error, from the line of a streamed input it happened on. Parse errors already
+    // say where they are.
+    Line { line: usize, error: Box<SolveError> },
+}
+
+impl Error for SolveError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            SolveError::Parse(e) => Some(e),
+            SolveError::Day(e) => Some(e.as_ref()),
+            SolveError::Other(e) => Some(e.as_ref()),
+            SolveError::Line { error, .. } => Some(error.as_ref()),
+            _ => None,
+        }
+    }
+}
+
+impl Display for SolveError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SolveError::Parse(e) => write!(f, "unable to parse input at {}", e),
+            SolveError::Unsolvable(message) => f.write_str(message),
+            SolveError::NoSuchPart { day, part } => {
+                write!(f, "day {} has no part {}", day, part)
+            }
+            SolveError::Day(e) => write!(f, "{}", e),
+            SolveError::Other(e) => write!(f, "{}", e),
+            SolveError::Line { line, error } => write!(f, "line {}: {}", line, error),
+        }
+    }
+}
+
+impl SolveError {
+    // The day that raised the error, when it was one of the day's own.
+    pub fn day(&self) -> Option<u8> {
+        match self {
+            SolveError::Day(e) => Some(e.day()),
+            SolveError::NoSuchPart { day, .. } => Some(*day),
+            SolveError::Line { error, .. } => error.day(),
+            _ => None,
+        }
+    }
+}
+
+// Every day with ways to fail beyond a parse error has an `Error` enum implementing this, which
+// lets `?` turn it into a `SolveError`.
+pub trait DayError: Error + Send + Sync + 'static {
+    fn day(&self) -> u8;
+}
+
+impl<E: DayError> From<E> for SolveError {
+    fn from(e: E) -> SolveError {
+        SolveError::Day(Box::new(e))
+    }
+}
+
+impl From<ParseError> for SolveError {
+    fn from(e: ParseError) -> SolveError {
+        SolveError::Parse(e)
+    }
+}
+
+impl From<&str> for SolveError {
+    fn from(message: &str) -> SolveError {
+        SolveError::Unsolvable(message.to_owned())
+    }
+}
+
+impl From<String> for SolveError {
+    fn from(message: String) -> SolveError {
+        SolveError::Unsolvable(message)
+    }
+}
+
+// Each day implements this on a unit struct, so the runner can treat them all the same way. The
+// input is the full puzzle input.
+pub trait Solution: Sync {
+    fn day(&self) -> u8;
+
+    fn title(&self) -> &'static str;
+
+    // Only parses the input, so that parsing can be timed apart from solving.
+    fn parse(&self, input: &str) -> Result<(), SolveError>;
+
+    fn part1(&self, input: &str) -> Result<Answer, SolveError>;
+
+    fn part2(&self, input: &str) -> Result<Answer, SolveError>;
+
+    // The parts that have a puzzle. Only day 25 is missing one.
+    fn parts(&self) -> &'static [Part] {
+        &Part::ALL
+    }
+
+    fn solve(&self, part: Part, input: &str) -> Result<Answer, SolveError> {
+        match part {
+            Part::One => self.part1(input),
+            Part::Two => self.part2(input),
+        }
+    }
+}
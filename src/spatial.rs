@@ -0,0 +1,639 @@
+use std::fmt::Debug;
+
+use crate::geom::{Point2, Point3};
+
+// The lowest and highest corners of a box, both inside it.
+pub type Bounds<P> = (P, P);
+
+// Points that a tree can split space around, halving it along every axis at once.
+pub trait Coords: Copy + Eq + Default + Debug {
+    // How many parts a box splits into: 4 on the plane, 8 in space.
+    const CHILDREN: usize;
+
+    // On or below `other` along every axis.
+    fn all_le(self, other: Self) -> bool;
+
+    // Where to split a box, as the lowest corner of its upper half. An axis only one wide has
+    // nothing in its lower half.
+    fn pivot(bounds: Bounds<Self>) -> Self;
+
+    // Which part of a box split at `pivot` this is in, with a bit set for each axis where it's in
+    // the upper half.
+    fn child(self, pivot: Self) -> usize;
+
+    fn child_bounds(bounds: Bounds<Self>, pivot: Self, child: usize) -> Bounds<Self>;
+
+    // Bigger bounds that take in `self`, growing threefold along each axis that needs it so that
+    // it doesn't have to happen often.
+    fn grow(self, bounds: Bounds<Self>) -> Bounds<Self>;
+
+    // The smallest bounds taking in both `self` and `bounds`.
+    fn envelop(self, bounds: Bounds<Self>) -> Bounds<Self>;
+
+    // The point in the box nearest to this one.
+    fn closest_in(self, bounds: Bounds<Self>) -> Self;
+
+    fn distance_squared(self, other: Self) -> i64;
+}
+
+fn pivot_axis(min: i64, max: i64) -> i64 {
+    min + (max - min + 1) / 2
+}
+
+fn half_axis(min: i64, max: i64, pivot: i64, upper: bool) -> (i64, i64) {
+    if upper {
+        (pivot, max)
+    } else {
+        (min, pivot - 1)
+    }
+}
+
+fn grow_axis(mut min: i64, mut max: i64, n: i64) -> (i64, i64) {
+    while n < min || n > max {
+        let extent = max - min + 1;
+        min -= extent;
+        max += extent;
+    }
+
+    (min, max)
+}
+
+impl Coords for Point2 {
+    const CHILDREN: usize = 4;
+
+    fn all_le(self, other: Point2) -> bool {
+        self.x <= other.x && self.y <= other.y
+    }
+
+    fn pivot((min, max): Bounds<Point2>) -> Point2 {
+        Point2::new(pivot_axis(min.x, max.x), pivot_axis(min.y, max.y))
+    }
+
+    fn child(self, pivot: Point2) -> usize {
+        (self.x >= pivot.x) as usize | ((self.y >= pivot.y) as usize) << 1
+    }
+
+    fn child_bounds((min, max): Bounds<Point2>, pivot: Point2, child: usize) -> Bounds<Point2> {
+        let (x0, x1) = half_axis(min.x, max.x, pivot.x, child & 1 != 0);
+        let (y0, y1) = half_axis(min.y, max.y, pivot.y, child & 2 != 0);
+        (Point2::new(x0, y0), Point2::new(x1, y1))
+    }
+
+    fn grow(self, (min, max): Bounds<Point2>) -> Bounds<Point2> {
+        let (x0, x1) = grow_axis(min.x, max.x, self.x);
+        let (y0, y1) = grow_axis(min.y, max.y, self.y);
+        (Point2::new(x0, y0), Point2::new(x1, y1))
+    }
+
+    fn envelop(self, (min, max): Bounds<Point2>) -> Bounds<Point2> {
+        (
+            Point2::new(min.x.min(self.x), min.y.min(self.y)),
+            Point2::new(max.x.max(self.x), max.y.max(self.y)),
+        )
+    }
+
+    fn closest_in(self, (min, max): Bounds<Point2>) -> Point2 {
+        Point2::new(self.x.max(min.x).min(max.x), self.y.max(min.y).min(max.y))
+    }
+
+    fn distance_squared(self, other: Point2) -> i64 {
+        let d = other - self;
+        d.x * d.x + d.y * d.y
+    }
+}
+
+impl Coords for Point3 {
+    const CHILDREN: usize = 8;
+
+    fn all_le(self, other: Point3) -> bool {
+        self.x <= other.x && self.y <= other.y && self.z <= other.z
+    }
+
+    fn pivot((min, max): Bounds<Point3>) -> Point3 {
+        Point3::new(
+            pivot_axis(min.x, max.x),
+            pivot_axis(min.y, max.y),
+            pivot_axis(min.z, max.z),
+        )
+    }
+
+    fn child(self, pivot: Point3) -> usize {
+        (self.x >= pivot.x) as usize
+            | ((self.y >= pivot.y) as usize) << 1
+            | ((self.z >= pivot.z) as usize) << 2
+    }
+
+    fn child_bounds((min, max): Bounds<Point3>, pivot: Point3, child: usize) -> Bounds<Point3> {
+        let (x0, x1) = half_axis(min.x, max.x, pivot.x, child & 1 != 0);
+        let (y0, y1) = half_axis(min.y, max.y, pivot.y, child & 2 != 0);
+        let (z0, z1) = half_axis(min.z, max.z, pivot.z, child & 4 != 0);
+        (Point3::new(x0, y0, z0), Point3::new(x1, y1, z1))
+    }
+
+    fn grow(self, (min, max): Bounds<Point3>) -> Bounds<Point3> {
+        let (x0, x1) = grow_axis(min.x, max.x, self.x);
+        let (y0, y1) = grow_axis(min.y, max.y, self.y);
+        let (z0, z1) = grow_axis(min.z, max.z, self.z);
+        (Point3::new(x0, y0, z0), Point3::new(x1, y1, z1))
+    }
+
+    fn envelop(self, (min, max): Bounds<Point3>) -> Bounds<Point3> {
+        (
+            Point3::new(min.x.min(self.x), min.y.min(self.y), min.z.min(self.z)),
+            Point3::new(max.x.max(self.x), max.y.max(self.y), max.z.max(self.z)),
+        )
+    }
+
+    fn closest_in(self, (min, max): Bounds<Point3>) -> Point3 {
+        Point3::new(
+            self.x.max(min.x).min(max.x),
+            self.y.max(min.y).min(max.y),
+            self.z.max(min.z).min(max.z),
+        )
+    }
+
+    fn distance_squared(self, other: Point3) -> i64 {
+        let d = other - self;
+        d.x * d.x + d.y * d.y + d.z * d.z
+    }
+}
+
+pub type Quadtree<T> = Tree<Point2, T>;
+pub type Octree<T> = Tree<Point3, T>;
+
+const DEFAULT_LEAF_CAPACITY: usize = 16;
+
+// Objects at points on the plane or in space, found by where they are. There can be more than one
+// object at the same point. The bounds grow to take in whatever is inserted.
+#[derive(Debug)]
+pub struct Tree<P, T> {
+    root: Node<P, T>,
+    len: usize,
+    leaf_capacity: usize,
+}
+
+impl<P: Coords, T> Tree<P, T> {
+    pub fn new() -> Tree<P, T> {
+        Tree::with_leaf_capacity(DEFAULT_LEAF_CAPACITY)
+    }
+
+    // How many objects a leaf can hold before it's split up.
+    pub fn with_leaf_capacity(leaf_capacity: usize) -> Tree<P, T> {
+        Tree {
+            root: Node::empty(),
+            len: 0,
+            leaf_capacity,
+        }
+    }
+
+    // Quicker than inserting one at a time, as it doesn't have to keep growing and splitting.
+    pub fn bulk_load<I>(leaf_capacity: usize, objects: I) -> Tree<P, T>
+    where
+        I: IntoIterator<Item = (P, T)>,
+    {
+        let objects = objects.into_iter().collect::<Vec<_>>();
+        let bounds = match objects.first() {
+            Some(&(first, _)) => objects
+                .iter()
+                .fold((first, first), |bounds, &(pos, _)| pos.envelop(bounds)),
+            None => (P::default(), P::default()),
+        };
+
+        Tree {
+            len: objects.len(),
+            root: Node::build(bounds, objects, leaf_capacity),
+            leaf_capacity,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    // The space the tree covers at the moment, which takes in every object and maybe more.
+    pub fn bounds(&self) -> Bounds<P> {
+        self.root.bounds()
+    }
+
+    pub fn insert(&mut self, position: P, object: T) {
+        let bounds = self.bounds();
+        if position.envelop(bounds) != bounds {
+            let objects = std::mem::replace(&mut self.root, Node::empty()).into_objects();
+            self.root = Node::build(position.grow(bounds), objects, self.leaf_capacity);
+        }
+
+        self.root.insert(position, object, self.leaf_capacity);
+        self.len += 1;
+    }
+
+    // Removes one of the objects at `position` that matches.
+    pub fn remove<F>(&mut self, position: P, mut f: F) -> Option<T>
+    where
+        F: FnMut(&T) -> bool,
+    {
+        let object = self.root.remove(position, &mut f)?;
+        self.len -= 1;
+        Some(object)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (P, &T)> {
+        self.search(Everywhere)
+    }
+
+    // Everything inside `bounds`, edges included.
+    pub fn query(&self, bounds: Bounds<P>) -> impl Iterator<Item = (P, &T)> {
+        self.search(Within(bounds))
+    }
+
+    // Everything no further than `radius` from `centre`, in a straight line.
+    pub fn within_radius(&self, centre: P, radius: i64) -> impl Iterator<Item = (P, &T)> {
+        self.search(Ball {
+            centre,
+            radius_squared: radius * radius,
+        })
+    }
+
+    // The object closest to `position` in a straight line, if there are any. Ties go to whichever
+    // is found first.
+    pub fn nearest(&self, position: P) -> Option<(P, &T)> {
+        let mut best = None;
+        self.root.nearest(position, &mut best);
+        best.map(|(_, pos, object)| (pos, object))
+    }
+
+    fn search<R: Region<P>>(&self, region: R) -> Search<'_, P, T, R> {
+        Search {
+            stack: vec![(&self.root, 0)],
+            region,
+        }
+    }
+}
+
+impl<P: Coords, T> Default for Tree<P, T> {
+    fn default() -> Tree<P, T> {
+        Tree::new()
+    }
+}
+
+impl<P: Coords, T> FromIterator<(P, T)> for Tree<P, T> {
+    fn from_iter<I: IntoIterator<Item = (P, T)>>(iter: I) -> Tree<P, T> {
+        Tree::bulk_load(DEFAULT_LEAF_CAPACITY, iter)
+    }
+}
+
+#[derive(Debug)]
+enum Node<P, T> {
+    Leaf {
+        bounds: Bounds<P>,
+        objects: Vec<(P, T)>,
+    },
+    Branch {
+        bounds: Bounds<P>,
+        pivot: P,
+        children: Vec<Node<P, T>>,
+    },
+}
+
+impl<P: Coords, T> Node<P, T> {
+    fn empty() -> Node<P, T> {
+        Node::Leaf {
+            bounds: (P::default(), P::default()),
+            objects: vec![],
+        }
+    }
+
+    // Splits up until no leaf holds more than it can, unless they're all at the same point.
+    fn build(bounds: Bounds<P>, objects: Vec<(P, T)>, leaf_capacity: usize) -> Node<P, T> {
+        if objects.len() <= leaf_capacity || bounds.0 == bounds.1 {
+            return Node::Leaf { bounds, objects };
+        }
+
+        let pivot = P::pivot(bounds);
+        let mut parts = (0..P::CHILDREN).map(|_| vec![]).collect::<Vec<_>>();
+        for (pos, object) in objects {
+            parts[pos.child(pivot)].push((pos, object));
+        }
+
+        let children = parts
+            .into_iter()
+            .enumerate()
+            .map(|(i, objects)| {
+                Node::build(P::child_bounds(bounds, pivot, i), objects, leaf_capacity)
+            })
+            .collect();
+
+        Node::Branch {
+            bounds,
+            pivot,
+            children,
+        }
+    }
+
+    fn bounds(&self) -> Bounds<P> {
+        match self {
+            Node::Leaf { bounds, .. } | Node::Branch { bounds, .. } => *bounds,
+        }
+    }
+
+    fn into_objects(self) -> Vec<(P, T)> {
+        match self {
+            Node::Leaf { objects, .. } => objects,
+            Node::Branch { children, .. } => {
+                children.into_iter().flat_map(Node::into_objects).collect()
+            }
+        }
+    }
+
+    fn insert(&mut self, position: P, object: T, leaf_capacity: usize) {
+        match self {
+            Node::Leaf { bounds, objects } => {
+                objects.push((position, object));
+
+                if objects.len() > leaf_capacity {
+                    *self = Node::build(*bounds, std::mem::take(objects), leaf_capacity);
+                }
+            }
+            Node::Branch {
+                pivot, children, ..
+            } => {
+                children[position.child(*pivot)].insert(position, object, leaf_capacity);
+            }
+        }
+    }
+
+    fn remove<F>(&mut self, position: P, f: &mut F) -> Option<T>
+    where
+        F: FnMut(&T) -> bool,
+    {
+        match self {
+            Node::Leaf { objects, .. } => {
+                let i = objects
+                    .iter()
+                    .position(|(pos, obj)| *pos == position && f(obj))?;
+                Some(objects.swap_remove(i).1)
+            }
+            Node::Branch {
+                pivot, children, ..
+            } => children[position.child(*pivot)].remove(position, f),
+        }
+    }
+
+    // Keeps `best` as the closest found so far, with its distance squared, and doesn't bother
+    // looking anywhere that couldn't beat it.
+    fn nearest<'a>(&'a self, position: P, best: &mut Option<(i64, P, &'a T)>) {
+        match self {
+            Node::Leaf { objects, .. } => {
+                for (pos, object) in objects {
+                    let d = pos.distance_squared(position);
+                    if best.is_none_or(|(closest, _, _)| d < closest) {
+                        *best = Some((d, *pos, object));
+                    }
+                }
+            }
+            Node::Branch { children, .. } => {
+                let mut children = children
+                    .iter()
+                    .map(|child| {
+                        let d = position
+                            .closest_in(child.bounds())
+                            .distance_squared(position);
+                        (d, child)
+                    })
+                    .collect::<Vec<_>>();
+                children.sort_by_key(|&(d, _)| d);
+
+                for (d, child) in children {
+                    if best.is_some_and(|(closest, _, _)| d > closest) {
+                        break;
+                    }
+                    child.nearest(position, best);
+                }
+            }
+        }
+    }
+}
+
+// Somewhere to look for objects.
+trait Region<P> {
+    // Whether any of the box could be in the region.
+    fn touches(&self, bounds: Bounds<P>) -> bool;
+    fn contains(&self, position: P) -> bool;
+}
+
+struct Everywhere;
+
+impl<P> Region<P> for Everywhere {
+    fn touches(&self, _bounds: Bounds<P>) -> bool {
+        true
+    }
+
+    fn contains(&self, _position: P) -> bool {
+        true
+    }
+}
+
+struct Within<P>(Bounds<P>);
+
+impl<P: Coords> Region<P> for Within<P> {
+    fn touches(&self, (min, max): Bounds<P>) -> bool {
+        min.all_le(self.0 .1) && self.0 .0.all_le(max)
+    }
+
+    fn contains(&self, position: P) -> bool {
+        self.0 .0.all_le(position) && position.all_le(self.0 .1)
+    }
+}
+
+struct Ball<P> {
+    centre: P,
+    radius_squared: i64,
+}
+
+impl<P: Coords> Region<P> for Ball<P> {
+    fn touches(&self, bounds: Bounds<P>) -> bool {
+        self.contains(self.centre.closest_in(bounds))
+    }
+
+    fn contains(&self, position: P) -> bool {
+        position.distance_squared(self.centre) <= self.radius_squared
+    }
+}
+
+struct Search<'a, P, T, R> {
+    stack: Vec<(&'a Node<P, T>, usize)>,
+    region: R,
+}
+
+impl<'a, P: Coords, T, R: Region<P>> Iterator for Search<'a, P, T, R> {
+    type Item = (P, &'a T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some((node, index)) = self.stack.pop() {
+            match node {
+                Node::Leaf { objects, .. } => {
+                    if let Some((position, object)) = objects.get(index) {
+                        self.stack.push((node, index + 1));
+
+                        if self.region.contains(*position) {
+                            return Some((*position, object));
+                        }
+                    }
+                }
+                Node::Branch {
+                    bounds, children, ..
+                } => {
+                    if self.region.touches(*bounds) {
+                        if let Some(child) = children.get(index) {
+                            self.stack.push((node, index + 1));
+                            self.stack.push((child, 0));
+                        }
+                    }
+                }
+            }
+        }
+
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use itertools::Itertools;
+
+    use super::*;
+
+    // Every pair of capital letters, at the point given by their character codes.
+    fn letters() -> Vec<(Point2, String)> {
+        (b'A'..=b'Z')
+            .cartesian_product(b'A'..=b'Z')
+            .map(|(x, y)| {
+                (
+                    Point2::new(x as i64, y as i64),
+                    String::from_utf8(vec![x, y]).unwrap(),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_quadtree() {
+        let items = letters();
+        let mut tree = Quadtree::new();
+
+        for (position, object) in items.iter() {
+            tree.insert(*position, object.clone());
+        }
+
+        assert_eq!(tree.len(), 26 * 26);
+        assert_eq!(
+            tree.iter()
+                .map(|(a, b)| (a, b.clone()))
+                .collect::<HashMap<Point2, String>>(),
+            items.iter().cloned().collect()
+        );
+
+        for i in b'A'..b'Y' {
+            let i = i as i64;
+            let found = tree
+                .query((Point2::new(i, i), Point2::new(i + 1, i + 1)))
+                .map(|(a, _)| a)
+                .sorted()
+                .collect::<Vec<_>>();
+
+            assert_eq!(
+                found,
+                vec![
+                    Point2::new(i, i),
+                    Point2::new(i, i + 1),
+                    Point2::new(i + 1, i),
+                    Point2::new(i + 1, i + 1),
+                ]
+            );
+        }
+
+        let position = Point2::new(b'C' as i64, b'D' as i64);
+        assert_eq!(tree.remove(position, |s| s == "CD"), Some("CD".to_owned()));
+        assert_eq!(tree.remove(position, |s| s == "CD"), None);
+        assert_eq!(tree.query((position, position)).count(), 0);
+        assert_eq!(tree.len(), 26 * 26 - 1);
+    }
+
+    #[test]
+    fn test_growing() {
+        let mut tree = Quadtree::with_leaf_capacity(2);
+
+        for (i, &(x, y)) in [(0, 0), (-50, 7), (1000, -3), (3, 3), (3, 3), (3, 3)]
+            .iter()
+            .enumerate()
+        {
+            tree.insert(Point2::new(x, y), i);
+        }
+
+        let (min, max) = tree.bounds();
+        assert!(min.all_le(Point2::new(-50, -3)) && Point2::new(1000, 7).all_le(max));
+        assert_eq!(
+            tree.iter().map(|(_, &i)| i).sorted().collect_vec(),
+            vec![0, 1, 2, 3, 4, 5]
+        );
+        assert_eq!(
+            tree.query((Point2::new(3, 3), Point2::new(3, 3))).count(),
+            3
+        );
+
+        // Loading all at once finds the same things.
+        let loaded: Quadtree<_> = letters().into_iter().collect();
+        assert_eq!(loaded.len(), 26 * 26);
+        assert_eq!(
+            loaded
+                .query((Point2::new(0, 0), Point2::new(b'B' as i64, b'B' as i64)))
+                .count(),
+            4
+        );
+    }
+
+    #[test]
+    fn test_nearest_and_radius() {
+        let tree: Quadtree<_> = letters().into_iter().collect();
+
+        let (pos, name) = tree.nearest(Point2::new(0, 0)).unwrap();
+        assert_eq!((pos, name.as_str()), (Point2::new(65, 65), "AA"));
+
+        let (_, name) = tree.nearest(Point2::new(70, 1000)).unwrap();
+        assert_eq!(name, "FZ");
+
+        let near = tree.within_radius(Point2::new(70, 70), 1).count();
+        assert_eq!(near, 5);
+        assert_eq!(tree.within_radius(Point2::new(70, 70), 2).count(), 13);
+
+        assert_eq!(Quadtree::<()>::new().nearest(Point2::ORIGIN), None);
+    }
+
+    #[test]
+    fn test_octree() {
+        // A 4x4x4 block of cubes, one at each point.
+        let cubes = (0..4)
+            .cartesian_product(0..4)
+            .cartesian_product(0..4)
+            .map(|((x, y), z)| Point3::new(x, y, z));
+        let tree: Octree<_> = cubes.map(|p| (p, ())).collect();
+
+        // The cubes sharing a face with one in the middle, and the one itself.
+        assert_eq!(tree.within_radius(Point3::new(1, 1, 1), 1).count(), 7);
+        assert_eq!(tree.within_radius(Point3::new(0, 0, 0), 1).count(), 4);
+        assert_eq!(
+            tree.query((Point3::new(1, 1, 1), Point3::new(2, 2, 9)))
+                .count(),
+            12
+        );
+        assert_eq!(
+            tree.nearest(Point3::new(10, -3, 2)).map(|(p, _)| p),
+            Some(Point3::new(3, 0, 2))
+        );
+    }
+}
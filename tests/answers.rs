@@ -0,0 +1,94 @@
+// Runs every day against its puzzle input in `data/`, and checks the answers against
+// `data/answers.toml`.
+//
+// A few days take minutes rather than seconds, so they're ignored by default. Run them with:
+//
+//     cargo test --release --test answers -- --include-ignored
+
+use std::path::PathBuf;
+
+use aoc::input::{self, Source};
+use aoc::{days, Answer, Part};
+
+fn data_dir() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("data")
+}
+
+fn expected_answer(day: u8, part: Part) -> Answer {
+    let manifest = std::fs::read_to_string(data_dir().join("answers.toml"))
+        .expect("unable to read answers.toml");
+    let manifest: toml::Table = manifest.parse().expect("answers.toml is not valid TOML");
+
+    let value = manifest
+        .get(&format!("day{:02}", day))
+        .and_then(|day| day.get(format!("part{}", part)))
+        .unwrap_or_else(|| panic!("no answer recorded for day {} part {}", day, part));
+
+    match value {
+        toml::Value::Integer(n) => Answer::Number(*n as i128),
+        toml::Value::String(s) => Answer::Text(s.clone()),
+        other => panic!("unexpected answer for day {} part {}: {}", day, part, other),
+    }
+}
+
+fn check(day: u8) {
+    let solution = days::get(day).expect("solution for every day");
+    let input = Source::Path(input::day_path(data_dir(), day))
+        .load()
+        .expect("unable to read puzzle input");
+
+    for &part in solution.parts() {
+        let answer = solution
+            .solve(part, &input)
+            .unwrap_or_else(|e| panic!("day {} part {} failed: {}", day, part, e));
+
+        assert_eq!(
+            answer,
+            expected_answer(day, part),
+            "day {} part {}",
+            day,
+            part
+        );
+    }
+}
+
+macro_rules! answer_tests {
+    ($($(#[$attr:meta])* $name:ident: $day:expr,)*) => {
+        $(
+            $(#[$attr])*
+            #[test]
+            fn $name() {
+                check($day);
+            }
+        )*
+    };
+}
+
+answer_tests! {
+    day01: 1,
+    day02: 2,
+    day03: 3,
+    day04: 4,
+    day05: 5,
+    day06: 6,
+    day07: 7,
+    day08: 8,
+    day09: 9,
+    day10: 10,
+    day11: 11,
+    day12: 12,
+    day13: 13,
+    day14: 14,
+    day15: 15,
+    day16: 16,
+    day17: 17,
+    day18: 18,
+    #[ignore = "slow"]
+    day19: 19,
+    day20: 20,
+    day21: 21,
+    day22: 22,
+    day23: 23,
+    day24: 24,
+    day25: 25,
+}